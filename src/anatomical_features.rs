@@ -1,6 +1,7 @@
 use crate::{primitives::*, skeletal::BoneSegment, tissue_muscle::TissueEnvelope};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SensoryType {
     Ocular,
     Auditory,
@@ -8,39 +9,45 @@ pub enum SensoryType {
     Tactile,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SensoryOrgan {
     pub kind: SensoryType,
     pub size: Length,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Protrusion {
     pub length: Length,
     pub curvature: Curve,
     pub tissue: TissueEnvelope,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpineRow {
     pub count: Count,
     pub spine_length: Length,
     pub spacing: Length,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FinStructure {
     pub height: Length,
     pub length: Length,
     pub membrane_thickness: Length,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MandibleStructure {
     pub segments: Vec<BoneSegment>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnatomicalFeature {
     Sensory(SensoryOrgan),
     Horn(Protrusion),
@@ -1,5 +1,6 @@
 use crate::{appendage::Terminus, primitives::*, skeletal::BoneSegment, surface::Integument};
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AppendageClass {
     Forelimb,
     Hindlimb,
@@ -10,6 +11,7 @@ pub enum AppendageClass {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MembraneSpan {
     pub proximal_bone: usize,
     pub distal_bone: usize,
@@ -18,11 +20,13 @@ pub struct MembraneSpan {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Patagium {
     pub spans: Vec<MembraneSpan>,
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BranchPoint {
     pub parent_segment: usize,
     pub branch_count: Count,
@@ -30,6 +34,7 @@ pub struct BranchPoint {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LimbStructure {
     pub segments: Vec<BoneSegment>,
     pub branching: Option<BranchPoint>,
@@ -37,6 +42,7 @@ pub struct LimbStructure {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Appendage {
     pub class: AppendageClass,
     pub structure: LimbStructure,
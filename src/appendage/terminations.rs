@@ -1,5 +1,8 @@
-use crate::primitives::*;
+use crate::primitives::InvalidValue;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u8", into = "u8"))]
 pub struct DigitCount(u8);
 
 impl DigitCount {
@@ -11,7 +14,8 @@ impl DigitCount {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Terminus {
     Tapered,
     Claw { digits: DigitCount },
@@ -20,3 +24,20 @@ pub enum Terminus {
     Sucker,
     Pincer,
 }
+
+impl TryFrom<u8> for DigitCount {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(InvalidValue {
+            type_name: "DigitCount",
+            value: value as f32,
+        })
+    }
+}
+
+impl From<DigitCount> for u8 {
+    fn from(value: DigitCount) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::{asset::AssetEvent, platform::collections::HashSet, prelude::*};
+
+use crate::{organism::Organism, primitives::GenomeSeed, species::Species};
+
+/// Registers [`Species`] as an asset and keeps generated organisms in sync
+/// with the species they were generated from.
+///
+/// With the `ron` feature, `.species.ron` files load through [`SpeciesLoader`].
+/// Enable Bevy's `file_watcher` feature to have edits on disk regenerate every
+/// live organism of the changed species from its stored seed.
+pub struct SpeciesAssetPlugin;
+
+impl Plugin for SpeciesAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Species>()
+            .add_systems(Update, generate_organisms);
+
+        #[cfg(feature = "ron")]
+        app.register_asset_loader(SpeciesLoader);
+    }
+}
+
+/// Requests an organism generated from `species` with `seed`.
+///
+/// The component stays on the entity so the organism can be regenerated
+/// whenever the species asset or the seed changes.
+#[derive(Component, Clone, Debug)]
+#[require(Transform)]
+pub struct SpawnOrganism {
+    pub species: Handle<Species>,
+    pub seed: GenomeSeed,
+}
+
+/// The organism most recently generated for a [`SpawnOrganism`]
+#[derive(Component, Clone, Debug, Deref)]
+pub struct GeneratedOrganism(pub Organism);
+
+/// Generate organisms that are new, whose request changed, or whose species
+/// asset was modified
+pub(crate) fn generate_organisms(
+    mut commands: Commands,
+    mut species_events: MessageReader<AssetEvent<Species>>,
+    species_assets: Res<Assets<Species>>,
+    requests: Query<(Entity, Ref<SpawnOrganism>, Has<GeneratedOrganism>)>,
+) {
+    let modified: HashSet<AssetId<Species>> = species_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, request, generated) in &requests {
+        let stale = !generated || request.is_changed() || modified.contains(&request.species.id());
+        if !stale {
+            continue;
+        }
+
+        // Still loading; picked up again on a later frame
+        let Some(species) = species_assets.get(&request.species) else {
+            continue;
+        };
+
+        match species.generate(request.seed) {
+            Ok(organism) => {
+                commands.entity(entity).insert(GeneratedOrganism(organism));
+            }
+            Err(err) => {
+                warn!(
+                    "failed to generate {} from seed {:?}: {err}",
+                    species.name(),
+                    request.seed
+                );
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ron")]
+pub use loader::{SpeciesLoadError, SpeciesLoader};
+
+#[cfg(feature = "ron")]
+mod loader {
+    use std::fmt;
+
+    use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+
+    use crate::{
+        species::{Species, UnvalidatedSpecies},
+        validation_errors::SpeciesValidationErrors,
+    };
+
+    /// Loads `.species.ron` files into validated [`Species`] assets
+    #[derive(Default)]
+    pub struct SpeciesLoader;
+
+    #[derive(Debug)]
+    pub enum SpeciesLoadError {
+        Io(std::io::Error),
+        Ron(ron::error::SpannedError),
+        Invalid(SpeciesValidationErrors),
+    }
+
+    impl fmt::Display for SpeciesLoadError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SpeciesLoadError::Io(err) => write!(f, "could not read species file: {err}"),
+                SpeciesLoadError::Ron(err) => write!(f, "could not parse species file: {err}"),
+                SpeciesLoadError::Invalid(err) => write!(f, "{err}"),
+            }
+        }
+    }
+
+    impl std::error::Error for SpeciesLoadError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                SpeciesLoadError::Io(err) => Some(err),
+                SpeciesLoadError::Ron(err) => Some(err),
+                SpeciesLoadError::Invalid(err) => Some(err),
+            }
+        }
+    }
+
+    impl AssetLoader for SpeciesLoader {
+        type Asset = Species;
+        type Settings = ();
+        type Error = SpeciesLoadError;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &(),
+            _load_context: &mut LoadContext<'_>,
+        ) -> Result<Species, SpeciesLoadError> {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(SpeciesLoadError::Io)?;
+            let input: UnvalidatedSpecies =
+                ron::de::from_bytes(&bytes).map_err(SpeciesLoadError::Ron)?;
+            Species::new(input).map_err(SpeciesLoadError::Invalid)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["species.ron"]
+        }
+    }
+}
@@ -4,17 +4,20 @@ use crate::{
 };
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertebra {
     pub bone: Bone,
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpinalAttachment<T> {
     pub vertebra_index: VertebraIndex,
     pub socket: SymmetricSocket<T>,
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Spine {
     pub vertebrae: NonEmpty<Vertebra>,
     pub appendages: Vec<SpinalAttachment<Appendage>>,
@@ -22,6 +25,7 @@ pub struct Spine {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Torso {
     pub spine: Spine,
     pub base_tissue: TissueEnvelope,
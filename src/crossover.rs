@@ -0,0 +1,410 @@
+use bevy::prelude::{LinearRgba, Mix};
+use rand::Rng;
+
+use crate::{
+    appendage::{Appendage, LimbStructure},
+    body::{SpinalAttachment, Spine, Torso, Vertebra},
+    head::Cranium,
+    organism::{Organism, UnvalidatedOrganism},
+    primitives::*,
+    skeletal::{Bone, BoneSegment},
+    sockets_symmetry::{BilateralPair, Socket, SymmetricSocket},
+    species::Species,
+    surface::{Integument, Metallic, Roughness},
+    validation_errors::OrganismValidationErrors,
+};
+
+/// How a child combines the traits of its two parents
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrossoverPolicy {
+    /// Every trait comes from either parent with equal chance
+    #[default]
+    Uniform,
+    /// The head, the torso and each socket come whole from one parent
+    PerRegion,
+    /// Numeric traits land at a random point between the parents' values;
+    /// discrete traits are picked as with [`CrossoverPolicy::Uniform`]
+    Blend,
+}
+
+impl Species {
+    /// Breed the `index`-th child of two organisms of this species.
+    ///
+    /// The child's seed is [`GenomeSeed::offspring`] of the parents' seeds, and
+    /// every decision is drawn from it, so the same parents, policy and index
+    /// always give the same child. The child is checked with
+    /// [`Organism::validate`] before it is returned.
+    pub fn crossover(
+        &self,
+        a: &Organism,
+        b: &Organism,
+        policy: CrossoverPolicy,
+        index: u64,
+    ) -> Result<Organism, OrganismValidationErrors> {
+        let genome_seed = a.genome_seed.offspring(b.genome_seed, index);
+        let seed = genome_seed.derive("crossover");
+
+        let head = cross_cranium(
+            &a.head,
+            &b.head,
+            &mut Mixer::new(policy, seed.derive("head")),
+        );
+        let torso = cross_torso(&a.torso, &b.torso, policy, seed.derive("torso"));
+
+        Organism::validate(
+            UnvalidatedOrganism {
+                genome_seed,
+                symmetry: self.symmetry(),
+                head,
+                torso,
+            },
+            self,
+        )
+    }
+}
+
+/// Draws the per-trait decisions for one region of the child
+struct Mixer {
+    policy: CrossoverPolicy,
+    rng: GenomeRng,
+    /// The parent a [`CrossoverPolicy::PerRegion`] region comes from
+    region_from_b: bool,
+}
+
+impl Mixer {
+    fn new(policy: CrossoverPolicy, seed: GenomeSeed) -> Self {
+        let mut rng: GenomeRng = seed.into();
+        let region_from_b = rng.random_bool(0.5);
+        Self {
+            policy,
+            rng,
+            region_from_b,
+        }
+    }
+
+    /// A mixer with its own stream that stays in this mixer's region
+    fn within(&self, seed: GenomeSeed) -> Self {
+        Self {
+            policy: self.policy,
+            rng: seed.into(),
+            region_from_b: self.region_from_b,
+        }
+    }
+
+    fn takes_b(&mut self) -> bool {
+        match self.policy {
+            CrossoverPolicy::PerRegion => self.region_from_b,
+            CrossoverPolicy::Uniform | CrossoverPolicy::Blend => self.rng.random_bool(0.5),
+        }
+    }
+
+    fn pick<'p, T>(&mut self, a: &'p T, b: &'p T) -> &'p T {
+        if self.takes_b() { b } else { a }
+    }
+
+    /// Share of `b` in a blended trait, or `None` when the trait is picked
+    fn blend(&mut self) -> Option<f32> {
+        (self.policy == CrossoverPolicy::Blend).then(|| self.rng.random())
+    }
+
+    fn value(&mut self, a: f32, b: f32) -> f32 {
+        match self.blend() {
+            Some(mix) => a + (b - a) * mix,
+            None => *self.pick(&a, &b),
+        }
+    }
+
+    fn length(&mut self, a: Length, b: Length) -> Length {
+        Length::new(self.value(a.value(), b.value()))
+            .expect("blending positive lengths stays positive")
+    }
+
+    fn normalized(&mut self, a: Normalized, b: Normalized) -> Normalized {
+        Normalized::new(self.value(a.value(), b.value()).clamp(0.0, 1.0)).unwrap()
+    }
+
+    fn color(&mut self, a: LinearRgba, b: LinearRgba) -> LinearRgba {
+        match self.blend() {
+            Some(mix) => a.mix(&b, mix),
+            None => *self.pick(&a, &b),
+        }
+    }
+
+    fn count(&mut self, a: usize, b: usize) -> usize {
+        match self.blend() {
+            Some(mix) => (a as f32 + (b as f32 - a as f32) * mix).round() as usize,
+            None => *self.pick(&a, &b),
+        }
+    }
+}
+
+fn cross_cranium(a: &Cranium, b: &Cranium, mixer: &mut Mixer) -> Cranium {
+    let seed = GenomeSeed(mixer.rng.random());
+
+    let sensory_sockets = a
+        .sensory_sockets
+        .iter()
+        .zip(&b.sensory_sockets)
+        .enumerate()
+        .map(|(i, (a, b))| {
+            cross_symmetric(
+                a,
+                b,
+                mixer,
+                seed.derive("sensory").derive_index(i as u64),
+                pick,
+            )
+        })
+        .collect();
+
+    let mandible_socket = match (&a.mandible_socket, &b.mandible_socket) {
+        (Some(a), Some(b)) => Some(cross_socket(a, b, mixer, pick)),
+        (a, b) => mixer.pick(a, b).clone(),
+    };
+
+    let feature_sockets = a
+        .feature_sockets
+        .iter()
+        .zip(&b.feature_sockets)
+        .enumerate()
+        .map(|(i, (a, b))| {
+            cross_symmetric(
+                a,
+                b,
+                mixer,
+                seed.derive("feature").derive_index(i as u64),
+                pick,
+            )
+        })
+        .collect();
+
+    Cranium {
+        bone: cross_bone(&a.bone, &b.bone, mixer),
+        sensory_sockets,
+        mandible_socket,
+        feature_sockets,
+        integument: cross_integument(&a.integument, &b.integument, mixer),
+    }
+}
+
+fn cross_torso(a: &Torso, b: &Torso, policy: CrossoverPolicy, seed: GenomeSeed) -> Torso {
+    let mixer = &mut Mixer::new(policy, seed.derive("body"));
+
+    let count = mixer.count(a.spine.vertebrae.len(), b.spine.vertebrae.len());
+    let vertebrae = (0..count.max(1))
+        .map(
+            |i| match (a.spine.vertebrae.get(i), b.spine.vertebrae.get(i)) {
+                (Some(a), Some(b)) => Vertebra {
+                    bone: cross_bone(&a.bone, &b.bone, mixer),
+                },
+                (Some(only), None) | (None, Some(only)) => only.clone(),
+                (None, None) => unreachable!("child never outgrows both parents"),
+            },
+        )
+        .collect();
+
+    Torso {
+        spine: Spine {
+            vertebrae: NonEmpty::from_vec(vertebrae).expect("at least one vertebra"),
+            appendages: cross_attachments(
+                &a.spine.appendages,
+                &b.spine.appendages,
+                count,
+                policy,
+                seed.derive("appendages"),
+                cross_appendage,
+            ),
+            features: cross_attachments(
+                &a.spine.features,
+                &b.spine.features,
+                count,
+                policy,
+                seed.derive("features"),
+                pick,
+            ),
+        },
+        base_tissue: mixer.pick(&a.base_tissue, &b.base_tissue).clone(),
+        integument: cross_integument(&a.integument, &b.integument, mixer),
+    }
+}
+
+/// Pair up spinal attachments by vertebra and order on that vertebra, and
+/// cross each pair as its own region. Attachments only one parent has are
+/// inherited as they are; anything past the child's spine is dropped.
+fn cross_attachments<T: Clone>(
+    a: &[SpinalAttachment<T>],
+    b: &[SpinalAttachment<T>],
+    vertebra_count: usize,
+    policy: CrossoverPolicy,
+    seed: GenomeSeed,
+    cross: fn(&T, &T, &mut Mixer) -> T,
+) -> Vec<SpinalAttachment<T>> {
+    let keyed_a = keyed(a);
+    let keyed_b = keyed(b);
+
+    let from_a = keyed_a.iter().map(|(key, attachment)| {
+        let partner = keyed_b
+            .iter()
+            .find(|(other, _)| other == key)
+            .map(|(_, partner)| *partner);
+        (*key, *attachment, partner)
+    });
+    let only_b = keyed_b
+        .iter()
+        .filter(|(key, _)| keyed_a.iter().all(|(other, _)| other != key))
+        .map(|(key, attachment)| (*key, *attachment, None));
+
+    from_a
+        .chain(only_b)
+        .filter(|((vertebra, _), _, _)| (*vertebra as usize) < vertebra_count)
+        .enumerate()
+        .map(|(i, (_, attachment, partner))| match partner {
+            Some(partner) => {
+                let mixer = &mut Mixer::new(policy, seed.derive_index(i as u64));
+                let socket_seed = GenomeSeed(mixer.rng.random());
+                SpinalAttachment {
+                    vertebra_index: attachment.vertebra_index,
+                    socket: cross_symmetric(
+                        &attachment.socket,
+                        &partner.socket,
+                        mixer,
+                        socket_seed,
+                        cross,
+                    ),
+                }
+            }
+            None => attachment.clone(),
+        })
+        .collect()
+}
+
+/// Attachments keyed by vertebra and their order among that vertebra's attachments
+pub(crate) fn keyed<T>(
+    attachments: &[SpinalAttachment<T>],
+) -> Vec<((u8, usize), &SpinalAttachment<T>)> {
+    attachments
+        .iter()
+        .enumerate()
+        .map(|(i, attachment)| {
+            let vertebra = attachment.vertebra_index.0;
+            let order = attachments[..i]
+                .iter()
+                .filter(|earlier| earlier.vertebra_index.0 == vertebra)
+                .count();
+            ((vertebra, order), attachment)
+        })
+        .collect()
+}
+
+/// Both sides of a lateral pair, and every fold of a radial socket, draw from
+/// the same stream, so symmetric parents give a symmetric child
+fn cross_symmetric<T: Clone>(
+    a: &SymmetricSocket<T>,
+    b: &SymmetricSocket<T>,
+    region: &Mixer,
+    seed: GenomeSeed,
+    cross: fn(&T, &T, &mut Mixer) -> T,
+) -> SymmetricSocket<T> {
+    match (a, b) {
+        (SymmetricSocket::Medial(a), SymmetricSocket::Medial(b)) => {
+            SymmetricSocket::Medial(cross_socket(a, b, &mut region.within(seed), cross))
+        }
+        (SymmetricSocket::Lateral(a), SymmetricSocket::Lateral(b)) => {
+            SymmetricSocket::Lateral(BilateralPair {
+                left: cross_socket(&a.left, &b.left, &mut region.within(seed), cross),
+                right: cross_socket(&a.right, &b.right, &mut region.within(seed), cross),
+            })
+        }
+        (SymmetricSocket::Radial(a), SymmetricSocket::Radial(b)) if a.len() == b.len() => {
+            SymmetricSocket::Radial(
+                a.iter()
+                    .zip(b)
+                    .map(|(a, b)| cross_socket(a, b, &mut region.within(seed), cross))
+                    .collect(),
+            )
+        }
+        // Parents of one species share socket layouts
+        _ => a.clone(),
+    }
+}
+
+fn cross_socket<T: Clone>(
+    a: &Socket<T>,
+    b: &Socket<T>,
+    mixer: &mut Mixer,
+    cross: fn(&T, &T, &mut Mixer) -> T,
+) -> Socket<T> {
+    let attachment = match (&a.attachment, &b.attachment) {
+        (Some(a), Some(b)) => Some(cross(a, b, mixer)),
+        (a, b) => mixer.pick(a, b).clone(),
+    };
+
+    Socket {
+        position: a.position,
+        normal: a.normal,
+        attachment,
+    }
+}
+
+fn pick<T: Clone>(a: &T, b: &T, mixer: &mut Mixer) -> T {
+    mixer.pick(a, b).clone()
+}
+
+fn cross_appendage(a: &Appendage, b: &Appendage, mixer: &mut Mixer) -> Appendage {
+    if a.class != b.class {
+        return mixer.pick(a, b).clone();
+    }
+
+    // Segment count, branching and membranes all index into the segments, so
+    // they come together from one parent
+    let layout = mixer.pick(a, b);
+    let segments = layout
+        .structure
+        .segments
+        .iter()
+        .enumerate()
+        .map(
+            |(i, segment)| match (a.structure.segments.get(i), b.structure.segments.get(i)) {
+                (Some(a), Some(b)) => cross_segment(a, b, mixer),
+                _ => segment.clone(),
+            },
+        )
+        .collect();
+
+    Appendage {
+        class: a.class,
+        structure: LimbStructure {
+            segments,
+            branching: layout.structure.branching.clone(),
+            terminus: mixer
+                .pick(&a.structure.terminus, &b.structure.terminus)
+                .clone(),
+        },
+        patagium: layout.patagium.clone(),
+        integument: cross_integument(&a.integument, &b.integument, mixer),
+    }
+}
+
+fn cross_segment(a: &BoneSegment, b: &BoneSegment, mixer: &mut Mixer) -> BoneSegment {
+    BoneSegment {
+        bone: cross_bone(&a.bone, &b.bone, mixer),
+        distal_joint: mixer.pick(&a.distal_joint, &b.distal_joint).clone(),
+    }
+}
+
+fn cross_bone(a: &Bone, b: &Bone, mixer: &mut Mixer) -> Bone {
+    Bone {
+        length: mixer.length(a.length, b.length),
+        tissue: mixer.pick(&a.tissue, &b.tissue).clone(),
+    }
+}
+
+fn cross_integument(a: &Integument, b: &Integument, mixer: &mut Mixer) -> Integument {
+    Integument {
+        base_color: mixer.color(a.base_color, b.base_color),
+        pattern: *mixer.pick(&a.pattern, &b.pattern),
+        roughness: Roughness(mixer.normalized(a.roughness.0, b.roughness.0)),
+        metallic: Metallic(mixer.normalized(a.metallic.0, b.metallic.0)),
+    }
+}
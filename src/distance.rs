@@ -0,0 +1,361 @@
+use std::{collections::HashSet, hash::Hash, mem::discriminant};
+
+use bevy::prelude::LinearRgba;
+
+use crate::{
+    appendage::{Appendage, Terminus},
+    body::SpinalAttachment,
+    crossover::keyed,
+    organism::Organism,
+    primitives::*,
+    skeletal::BoneSegment,
+    sockets_symmetry::{Socket, SymmetricSocket},
+    species::{AppendageGenes, IntegumentGenes, Species},
+    surface::Integument,
+};
+
+/// Relative weight of each component of a genetic distance.
+///
+/// Every component is measured on a 0–1 scale and the distance is their
+/// weighted mean, so it is 0 for identical genomes and at most 1. Negative
+/// weights count as 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistanceWeights {
+    pub vertebra_count: f32,
+    pub bone_length: f32,
+    /// Which appendage class fills each socket
+    pub appendage_class: f32,
+    pub terminus: f32,
+    pub integument: f32,
+}
+
+impl Default for DistanceWeights {
+    fn default() -> Self {
+        Self {
+            vertebra_count: 1.0,
+            bone_length: 1.0,
+            appendage_class: 1.0,
+            terminus: 1.0,
+            integument: 1.0,
+        }
+    }
+}
+
+impl DistanceWeights {
+    fn combine(&self, components: [f32; 5]) -> f32 {
+        let weights = [
+            self.vertebra_count,
+            self.bone_length,
+            self.appendage_class,
+            self.terminus,
+            self.integument,
+        ]
+        .map(|weight| weight.max(0.0));
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        weights
+            .iter()
+            .zip(components)
+            .map(|(weight, component)| weight * component)
+            .sum::<f32>()
+            / total
+    }
+}
+
+impl Organism {
+    /// How different two organisms are, from 0 for identical bodies to 1.
+    ///
+    /// Appendage sockets are matched by vertebra and their order on it, so
+    /// the organisms may come from different species.
+    pub fn distance(&self, other: &Organism, weights: &DistanceWeights) -> f32 {
+        let (a, b) = (&self.torso.spine, &other.torso.spine);
+        let sockets = matched_sockets(&a.appendages, &b.appendages);
+        let filled = sockets.iter().filter_map(|pair| match pair {
+            (Some(a), Some(b)) => Some((*a, *b)),
+            _ => None,
+        });
+
+        let mut bones = Mean::default();
+        bones.add(relative(self.head.bone.length, other.head.bone.length));
+        for i in 0..a.vertebrae.len().max(b.vertebrae.len()) {
+            bones.add(match (a.vertebrae.get(i), b.vertebrae.get(i)) {
+                (Some(a), Some(b)) => relative(a.bone.length, b.bone.length),
+                _ => 1.0,
+            });
+        }
+        for (a, b) in filled.clone() {
+            segment_lengths(&mut bones, &a.structure.segments, &b.structure.segments);
+        }
+
+        let mut classes = Mean::default();
+        for (a, b) in &sockets {
+            let same = a.map(|a| a.class) == b.map(|b| b.class);
+            classes.add(if same { 0.0 } else { 1.0 });
+        }
+
+        let mut termini = Mean::default();
+        let mut integuments = Mean::default();
+        integuments.add(integument(&self.head.integument, &other.head.integument));
+        integuments.add(integument(&self.torso.integument, &other.torso.integument));
+        for (a, b) in filled {
+            termini.add(terminus(&a.structure.terminus, &b.structure.terminus));
+            integuments.add(integument(&a.integument, &b.integument));
+        }
+
+        weights.combine([
+            relative_count(a.vertebrae.len() as f32, b.vertebrae.len() as f32),
+            bones.value(),
+            classes.value(),
+            termini.value(),
+            integuments.value(),
+        ])
+    }
+}
+
+impl Species {
+    /// How different two species' genes are, from 0 for identical genes to 1.
+    ///
+    /// Ranges are compared by how much they overlap and allowed lists by how
+    /// many entries they share.
+    pub fn distance(&self, other: &Species, weights: &DistanceWeights) -> f32 {
+        let (a, b) = (&self.torso().spine, &other.torso().spine);
+
+        let mut bones = Mean::default();
+        bones.add(range(
+            &self.head().bone.length,
+            &other.head().bone.length,
+            |length| length.value(),
+        ));
+        bones.add(range(&a.vertebra.length, &b.vertebra.length, |length| {
+            length.value()
+        }));
+        let (appendages_a, appendages_b) = (allowed_appendages(self), allowed_appendages(other));
+        for (_, a) in &appendages_a {
+            if let Some((_, b)) = appendages_b.iter().find(|(_, b)| b.class == a.class) {
+                bones.add(range(
+                    &a.limb.segment.length,
+                    &b.limb.segment.length,
+                    |length| length.value(),
+                ));
+            }
+        }
+
+        let classes = |appendages: &[(VertebraIndex, &AppendageGenes)]| {
+            appendages
+                .iter()
+                .map(|(vertebra, genes)| (*vertebra, genes.class))
+                .collect::<HashSet<_>>()
+        };
+        let termini = |appendages: &[(VertebraIndex, &AppendageGenes)]| {
+            appendages
+                .iter()
+                .flat_map(|(_, genes)| genes.limb.allowed_termini.values().map(discriminant))
+                .collect::<HashSet<_>>()
+        };
+
+        let mut integuments = Mean::default();
+        integuments.add(integument_genes(
+            &self.head().integument,
+            &other.head().integument,
+        ));
+        integuments.add(integument_genes(
+            &self.torso().integument,
+            &other.torso().integument,
+        ));
+
+        let count = |species: &Species| {
+            (
+                species.min_vertebrae().value() as f32,
+                species.max_vertebrae().value() as f32 + 1.0,
+            )
+        };
+        weights.combine([
+            overlap(count(self), count(other)),
+            bones.value(),
+            jaccard(&classes(&appendages_a), &classes(&appendages_b)),
+            jaccard(&termini(&appendages_a), &termini(&appendages_b)),
+            integuments.value(),
+        ])
+    }
+}
+
+#[derive(Default)]
+struct Mean {
+    total: f32,
+    count: usize,
+}
+
+impl Mean {
+    fn add(&mut self, value: f32) {
+        self.total += value;
+        self.count += 1;
+    }
+
+    fn value(&self) -> f32 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total / self.count as f32
+        }
+    }
+}
+
+/// Appendages on both organisms' sockets, one entry per side, paired by
+/// vertebra and order on that vertebra
+fn matched_sockets<'a>(
+    a: &'a [SpinalAttachment<Appendage>],
+    b: &'a [SpinalAttachment<Appendage>],
+) -> Vec<(Option<&'a Appendage>, Option<&'a Appendage>)> {
+    let (a, b) = (keyed(a), keyed(b));
+    let mut keys: Vec<_> = a.iter().chain(&b).map(|(key, _)| *key).collect();
+    keys.sort();
+    keys.dedup();
+
+    let sides_at = |attachments: &[((u8, usize), &'a SpinalAttachment<Appendage>)], key| {
+        attachments
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, attachment)| sides(&attachment.socket))
+            .unwrap_or_default()
+    };
+    keys.into_iter()
+        .flat_map(|key| {
+            let (a, b) = (sides_at(&a, key), sides_at(&b, key));
+            (0..a.len().max(b.len())).map(move |i| {
+                (
+                    a.get(i).and_then(|socket| socket.attachment.as_ref()),
+                    b.get(i).and_then(|socket| socket.attachment.as_ref()),
+                )
+            })
+        })
+        .collect()
+}
+
+fn sides<T>(socket: &SymmetricSocket<T>) -> Vec<&Socket<T>> {
+    match socket {
+        SymmetricSocket::Medial(socket) => vec![socket],
+        SymmetricSocket::Lateral(pair) => vec![&pair.left, &pair.right],
+        SymmetricSocket::Radial(folds) => folds.iter().collect(),
+    }
+}
+
+/// Every appendage a species allows, with the vertebrae it may attach to
+fn allowed_appendages(species: &Species) -> Vec<(VertebraIndex, &AppendageGenes)> {
+    species
+        .torso()
+        .spine
+        .appendage_sockets
+        .iter()
+        .flat_map(|rule| {
+            rule.vertebra_indices
+                .iter()
+                .flat_map(|vertebra| rule.allowed.values().map(|genes| (*vertebra, genes)))
+        })
+        .collect()
+}
+
+fn segment_lengths(mean: &mut Mean, a: &[BoneSegment], b: &[BoneSegment]) {
+    for i in 0..a.len().max(b.len()) {
+        mean.add(match (a.get(i), b.get(i)) {
+            (Some(a), Some(b)) => relative(a.bone.length, b.bone.length),
+            _ => 1.0,
+        });
+    }
+}
+
+fn relative(a: Length, b: Length) -> f32 {
+    relative_count(a.value(), b.value())
+}
+
+/// Difference as a fraction of the larger value
+fn relative_count(a: f32, b: f32) -> f32 {
+    let larger = a.max(b);
+    if larger <= 0.0 {
+        0.0
+    } else {
+        (a - b).abs() / larger
+    }
+}
+
+fn terminus(a: &Terminus, b: &Terminus) -> f32 {
+    match (a, b) {
+        (Terminus::Claw { digits: a }, Terminus::Claw { digits: b })
+        | (Terminus::Paw { digits: a }, Terminus::Paw { digits: b }) => {
+            relative_count(a.value() as f32, b.value() as f32)
+        }
+        _ if discriminant(a) == discriminant(b) => 0.0,
+        _ => 1.0,
+    }
+}
+
+fn integument(a: &Integument, b: &Integument) -> f32 {
+    let pattern = if a.pattern == b.pattern { 0.0 } else { 1.0 };
+    (color(a.base_color, b.base_color)
+        + pattern
+        + (a.roughness.0.value() - b.roughness.0.value()).abs()
+        + (a.metallic.0.value() - b.metallic.0.value()).abs())
+        / 4.0
+}
+
+/// Euclidean distance in linear RGB, scaled so black to white is 1
+fn color(a: LinearRgba, b: LinearRgba) -> f32 {
+    let squared = (a.red - b.red).powi(2) + (a.green - b.green).powi(2) + (a.blue - b.blue).powi(2);
+    (squared / 3.0).sqrt().min(1.0)
+}
+
+fn integument_genes(a: &IntegumentGenes, b: &IntegumentGenes) -> f32 {
+    let channels = [
+        |color: &LinearRgba| color.red,
+        |color: &LinearRgba| color.green,
+        |color: &LinearRgba| color.blue,
+    ];
+    let color = channels
+        .iter()
+        .map(|channel| range(&a.base_color, &b.base_color, channel))
+        .sum::<f32>()
+        / channels.len() as f32;
+    let patterns = |genes: &IntegumentGenes| {
+        genes
+            .allowed_patterns
+            .values()
+            .copied()
+            .collect::<HashSet<_>>()
+    };
+    (color
+        + jaccard(&patterns(a), &patterns(b))
+        + range(&a.roughness, &b.roughness, |r| r.0.value())
+        + range(&a.metallic, &b.metallic, |m| m.0.value()))
+        / 4.0
+}
+
+fn range<T>(a: &ValueRange<T>, b: &ValueRange<T>, value: impl Fn(&T) -> f32) -> f32 {
+    let bounds = |range: &ValueRange<T>| {
+        let (min, max) = (value(&range.min), value(&range.max));
+        (min.min(max), min.max(max))
+    };
+    overlap(bounds(a), bounds(b))
+}
+
+/// One minus the overlap of two intervals as a fraction of their union.
+/// Single points count as overlapping only when they are equal.
+fn overlap(a: (f32, f32), b: (f32, f32)) -> f32 {
+    let union = a.1.max(b.1) - a.0.min(b.0);
+    if union <= 0.0 {
+        return 0.0;
+    }
+    let shared = (a.1.min(b.1) - a.0.max(b.0)).max(0.0);
+    1.0 - shared / union
+}
+
+/// One minus the shared fraction of two sets
+fn jaccard<T: Eq + Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        0.0
+    } else {
+        1.0 - a.intersection(b).count() as f32 / union as f32
+    }
+}
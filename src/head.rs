@@ -6,6 +6,7 @@ use crate::{
 };
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cranium {
     pub bone: Bone,
     pub sensory_sockets: Vec<SymmetricSocket<SensoryOrgan>>,
@@ -1,17 +1,29 @@
-mod anatomical_features;
-mod appendage;
-mod body;
-mod head;
-mod organism;
-mod primitives;
-mod skeletal;
+pub mod anatomical_features;
+pub mod appendage;
+pub mod asset;
+pub mod body;
+pub mod crossover;
+pub mod distance;
+pub mod head;
+pub mod mesh;
+pub mod mutation;
+pub mod organism;
+pub mod phylogeny;
+pub mod plugin;
+pub mod population;
+pub mod primitives;
+pub mod skeletal;
 pub mod skeleton;
-mod sockets_symmetry;
-mod species;
-mod surface;
-mod tissue_muscle;
-mod validation_errors;
+pub mod sockets_symmetry;
+pub mod speciation;
+pub mod species;
+pub mod surface;
+pub mod tissue_muscle;
+pub mod validation_errors;
 
-// Re-export key types for skeleton generation
+// Re-export key types for organism and skeleton generation
 pub use organism::Organism;
+pub use plugin::SpeciationPlugin;
+pub use primitives::Sample;
 pub use skeleton::{GeneratedSkeleton, SkeletonGenerator};
+pub use species::{Species, UnvalidatedSpecies};
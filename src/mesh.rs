@@ -0,0 +1,335 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    mesh::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues},
+    prelude::{Dir3, Mat4, Resource, Vec2, Vec3},
+};
+
+use crate::{
+    primitives::{CrossSectionProfile, Length},
+    skeleton::{BoneId, GeneratedSkeleton, SkeletonNode},
+    tissue_muscle::TissueEnvelope,
+};
+
+/// Resolution and skinning settings for tissue meshes
+#[derive(Clone, Debug, Resource)]
+pub struct TissueMeshConfig {
+    /// Vertices around each cross-section ring
+    pub radial_segments: u32,
+    /// Rings along the bone, minus one
+    pub length_segments: u32,
+    /// Close both ends of the tube
+    pub caps: bool,
+    /// Fraction of each bone, from its base, whose vertices are partly
+    /// weighted to the parent joint in skinned meshes. Zero skins rigidly.
+    pub joint_blend: f32,
+}
+
+impl Default for TissueMeshConfig {
+    fn default() -> Self {
+        Self {
+            radial_segments: 16,
+            length_segments: 8,
+            caps: true,
+            joint_blend: 0.25,
+        }
+    }
+}
+
+/// A single mesh for a whole skeleton, skinned to its bones
+pub struct SkinnedTissueMesh {
+    /// Mesh in skeleton space with joint indices and weights. Joint `i` is
+    /// the `i`th bone of [`GeneratedSkeleton::iter`].
+    pub mesh: Mesh,
+    /// Inverse of each joint's rest transform in skeleton space
+    pub inverse_bindposes: Vec<Mat4>,
+}
+
+/// Sweeps tissue cross-sections along bones to build meshes
+pub struct TissueMeshBuilder {
+    config: TissueMeshConfig,
+}
+
+impl TissueMeshBuilder {
+    pub fn new(config: TissueMeshConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn with_default_config() -> Self {
+        Self::new(TissueMeshConfig::default())
+    }
+
+    /// Build a mesh for every bone in the skeleton that carries tissue.
+    /// Each mesh is in the space of its own bone.
+    pub fn build_skeleton(&self, skeleton: &GeneratedSkeleton) -> Vec<(BoneId, Mesh)> {
+        skeleton
+            .iter()
+            .filter_map(|node| {
+                let tissue = node.tissue.as_ref()?;
+                let axis = Dir3::new(node.axis).ok()?;
+                Some((node.id.clone(), self.build(tissue, node.length, axis)))
+            })
+            .collect()
+    }
+
+    /// Build a mesh for one bone of `length` extending from the origin along `axis`
+    pub fn build(&self, tissue: &TissueEnvelope, length: Length, axis: Dir3) -> Mesh {
+        self.sweep(tissue, length, axis).into_mesh()
+    }
+
+    /// Build one mesh for the whole skeleton, posed at rest and skinned to
+    /// every bone. Vertices near the base of a bone blend into its parent
+    /// joint over [`TissueMeshConfig::joint_blend`] of the bone's length.
+    pub fn build_skinned(&self, skeleton: &GeneratedSkeleton) -> SkinnedTissueMesh {
+        let poses = bind_poses(skeleton);
+        let blend = self.config.joint_blend.clamp(0.0, 1.0);
+
+        let mut geometry = TubeGeometry::default();
+        let mut joint_indices = Vec::new();
+        let mut joint_weights = Vec::new();
+
+        for (joint, node) in skeleton.iter().enumerate() {
+            let Some(tissue) = node.tissue.as_ref() else {
+                continue;
+            };
+            let Ok(axis) = Dir3::new(node.axis) else {
+                continue;
+            };
+            let bone = self.sweep(tissue, node.length, axis);
+            let pose = &poses[joint];
+            let base = geometry.positions.len() as u32;
+
+            for (position, normal) in bone.positions.iter().zip(&bone.normals) {
+                let position = Vec3::from_array(*position);
+                let t = (position.dot(axis.as_vec3()) / node.length.value()).clamp(0.0, 1.0);
+                let (indices, weights) = joint_influence(joint, pose.parent, t, blend);
+                joint_indices.push(indices);
+                joint_weights.push(weights);
+
+                geometry
+                    .positions
+                    .push(pose.model.transform_point3(position).to_array());
+                geometry.normals.push(
+                    pose.model
+                        .transform_vector3(Vec3::from_array(*normal))
+                        .normalize_or_zero()
+                        .to_array(),
+                );
+            }
+            geometry.uvs.extend(bone.uvs);
+            geometry
+                .indices
+                .extend(bone.indices.iter().map(|index| base + index));
+        }
+
+        SkinnedTissueMesh {
+            mesh: geometry
+                .into_mesh()
+                .with_inserted_attribute(
+                    Mesh::ATTRIBUTE_JOINT_INDEX,
+                    VertexAttributeValues::Uint16x4(joint_indices),
+                )
+                .with_inserted_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, joint_weights),
+            inverse_bindposes: poses.iter().map(|pose| pose.model.inverse()).collect(),
+        }
+    }
+
+    pub(crate) fn sweep(
+        &self,
+        tissue: &TissueEnvelope,
+        length: Length,
+        axis: Dir3,
+    ) -> TubeGeometry {
+        let radial = self.config.radial_segments.max(3);
+        let rings = self.config.length_segments.max(1) + 1;
+        let along = axis.as_vec3();
+        let (side, _) = axis.any_orthonormal_pair();
+        let up = along.cross(side);
+
+        // Surface points on a grid; the seam column is duplicated for UVs
+        let mut grid = Vec::with_capacity((rings * (radial + 1)) as usize);
+        for ring in 0..rings {
+            let t = ring as f32 / (rings - 1) as f32;
+            let radius = tissue.radius_curve.evaluate(t);
+            for step in 0..=radial {
+                let angle = step as f32 / radial as f32 * TAU;
+                let shape = cross_section(&tissue.profile, angle);
+                let bulge = 1.0 + bulge_displacement(tissue, t, angle);
+                let offset = shape * radius * bulge;
+                grid.push(along * (t * length.value()) + side * offset.x + up * offset.y);
+            }
+        }
+
+        let columns = radial + 1;
+        let at = |ring: u32, step: u32| grid[(ring * columns + step) as usize];
+
+        let mut geometry = TubeGeometry::default();
+        for ring in 0..rings {
+            for step in 0..=radial {
+                let around_next = at(ring, (step + 1) % radial);
+                let around_prev = at(ring, (step + radial - 1) % radial);
+                let along_next = at((ring + 1).min(rings - 1), step);
+                let along_prev = at(ring.saturating_sub(1), step);
+
+                let angle = step as f32 / radial as f32 * TAU;
+                let fallback = side * angle.cos() + up * angle.sin();
+                let normal = (around_next - around_prev)
+                    .cross(along_next - along_prev)
+                    .try_normalize()
+                    .map(|n| if n.dot(fallback) < 0.0 { -n } else { n })
+                    .unwrap_or(fallback);
+
+                geometry.positions.push(at(ring, step).to_array());
+                geometry.normals.push(normal.to_array());
+                geometry.uvs.push([
+                    step as f32 / radial as f32,
+                    ring as f32 / (rings - 1) as f32,
+                ]);
+            }
+        }
+
+        for ring in 0..rings - 1 {
+            for step in 0..radial {
+                let a = ring * columns + step;
+                let b = a + 1;
+                let c = a + columns;
+                let d = c + 1;
+                geometry.indices.extend([a, b, c, b, d, c]);
+            }
+        }
+
+        if self.config.caps {
+            for (ring, outward) in [(0, -along), (rings - 1, along)] {
+                let center = geometry.positions.len() as u32;
+                let center_position = along * (ring as f32 / (rings - 1) as f32 * length.value());
+                geometry.positions.push(center_position.to_array());
+                geometry.normals.push(outward.to_array());
+                geometry.uvs.push([0.5, 0.5]);
+
+                let first = geometry.positions.len() as u32;
+                for step in 0..radial {
+                    let angle = step as f32 / radial as f32 * TAU;
+                    geometry.positions.push(at(ring, step).to_array());
+                    geometry.normals.push(outward.to_array());
+                    let uv = Vec2::new(angle.cos(), angle.sin()) * 0.5 + 0.5;
+                    geometry.uvs.push(uv.to_array());
+                }
+
+                for step in 0..radial {
+                    let current = first + step;
+                    let next = first + (step + 1) % radial;
+                    if ring == 0 {
+                        geometry.indices.extend([center, next, current]);
+                    } else {
+                        geometry.indices.extend([center, current, next]);
+                    }
+                }
+            }
+        }
+
+        geometry
+    }
+}
+
+/// Raw vertex data for a swept tube
+#[derive(Default)]
+pub(crate) struct TubeGeometry {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub uvs: Vec<[f32; 2]>,
+    pub indices: Vec<u32>,
+}
+
+impl TubeGeometry {
+    fn into_mesh(self) -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs)
+        .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+/// Rest pose of a bone in skeleton space
+struct BindPose {
+    model: Mat4,
+    parent: Option<usize>,
+}
+
+/// Bind poses in [`GeneratedSkeleton::iter`] order, accumulated down the
+/// [`RestTransform`](crate::skeleton::RestTransform) chain
+fn bind_poses(skeleton: &GeneratedSkeleton) -> Vec<BindPose> {
+    fn visit(node: &SkeletonNode, parent: Option<usize>, poses: &mut Vec<BindPose>) {
+        let local = Mat4::from_rotation_translation(node.rest.rotation, node.rest.translation);
+        let model = match parent {
+            Some(parent) => poses[parent].model * local,
+            None => local,
+        };
+        let index = poses.len();
+        poses.push(BindPose { model, parent });
+        for child in &node.children {
+            visit(child, Some(index), poses);
+        }
+    }
+
+    let mut poses = Vec::with_capacity(skeleton.bone_count());
+    visit(&skeleton.root, None, &mut poses);
+    poses
+}
+
+/// Joints and weights for a vertex at `t` along bone `joint`. Within `blend`
+/// of the base the parent takes a share that reaches half at the joint.
+fn joint_influence(
+    joint: usize,
+    parent: Option<usize>,
+    t: f32,
+    blend: f32,
+) -> ([u16; 4], [f32; 4]) {
+    match parent {
+        Some(parent) if t < blend => {
+            let share = 0.5 * (1.0 - t / blend);
+            (
+                [joint as u16, parent as u16, 0, 0],
+                [1.0 - share, share, 0.0, 0.0],
+            )
+        }
+        _ => ([joint as u16, 0, 0, 0], [1.0, 0.0, 0.0, 0.0]),
+    }
+}
+
+/// Unit cross-section outline at `angle` around the bone
+fn cross_section(profile: &CrossSectionProfile, angle: f32) -> Vec2 {
+    let direction = Vec2::new(angle.cos(), angle.sin());
+    match profile {
+        CrossSectionProfile::Circular => direction,
+        CrossSectionProfile::Elliptical(aspect) => direction * Vec2::new(aspect.value(), 1.0),
+        CrossSectionProfile::Radial(curve) => direction * curve.evaluate(angle / TAU),
+    }
+}
+
+/// Relative radius increase from muscle bulges at position `t` and `angle`.
+///
+/// Each bulge is a gaussian bump centred on its attachment, with `spread` as
+/// the standard deviation in units of bone length and full turns.
+fn bulge_displacement(tissue: &TissueEnvelope, t: f32, angle: f32) -> f32 {
+    tissue
+        .musculature
+        .iter()
+        .map(|bulge| {
+            let along = t - bulge.attachment.position.value();
+            let around = angular_distance(angle, bulge.attachment.radial_angle.value()) / TAU;
+            let spread = bulge.spread.value();
+            let falloff = (-(along * along + around * around) / (2.0 * spread * spread)).exp();
+            bulge.intensity.value() * falloff
+        })
+        .sum()
+}
+
+fn angular_distance(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(TAU);
+    difference.min(TAU - difference)
+}
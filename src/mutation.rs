@@ -0,0 +1,652 @@
+use bevy::{color::ColorToComponents, prelude::LinearRgba};
+use rand::Rng;
+
+use crate::{
+    appendage::{Appendage, LimbStructure},
+    body::{SpinalAttachment, Torso, Vertebra},
+    head::Cranium,
+    organism::{Organism, UnvalidatedOrganism},
+    primitives::*,
+    skeletal::Bone,
+    skeleton::Side,
+    sockets_symmetry::{Socket, SymmetricSocket},
+    species::{
+        AppendageGenes, BoneGenes, CraniumGenes, IntegumentGenes, LimbGenes, Species,
+        TissueEnvelopeGenes, UnvalidatedSpecies, fill_appendage_socket, fill_socket,
+        generate_appendage, generate_bone,
+    },
+    surface::{Integument, Metallic, Roughness},
+    tissue_muscle::MuscleIntensity,
+    validation_errors::{GenerationError, OrganismValidationErrors},
+};
+
+/// Chance of each kind of organism mutation, checked once per trait
+#[derive(Clone, Debug)]
+pub struct MutationRates {
+    /// Nudge a bone's length
+    pub length: Normalized,
+    /// Shift an integument's base color
+    pub color: Normalized,
+    /// Swap a limb's terminus for another allowed one
+    pub terminus: Normalized,
+    /// Add or drop the last vertebra
+    pub vertebra_count: Normalized,
+    /// Fill an empty optional socket or empty a filled one
+    pub socket_fill: Normalized,
+    /// Largest nudge, as a fraction of the gene's range
+    pub step: Normalized,
+}
+
+impl Default for MutationRates {
+    fn default() -> Self {
+        Self {
+            length: Normalized::new(0.1).unwrap(),
+            color: Normalized::new(0.1).unwrap(),
+            terminus: Normalized::new(0.05).unwrap(),
+            vertebra_count: Normalized::new(0.05).unwrap(),
+            socket_fill: Normalized::new(0.05).unwrap(),
+            step: Normalized::new(0.1).unwrap(),
+        }
+    }
+}
+
+/// Chance of each kind of species mutation, checked once per gene
+#[derive(Clone, Debug)]
+pub struct SpeciesMutationRates {
+    /// Widen or narrow a bone, tissue or surface range
+    pub range: Normalized,
+    /// Allow an appendage at a socket that another socket already allows
+    pub allowed_appendage: Normalized,
+    /// Toggle whether a socket must be filled
+    pub required: Normalized,
+    /// Largest relative change in a range's width
+    pub step: Normalized,
+}
+
+impl Default for SpeciesMutationRates {
+    fn default() -> Self {
+        Self {
+            range: Normalized::new(0.1).unwrap(),
+            allowed_appendage: Normalized::new(0.05).unwrap(),
+            required: Normalized::new(0.05).unwrap(),
+            step: Normalized::new(0.2).unwrap(),
+        }
+    }
+}
+
+impl Species {
+    /// Produce the `index`-th mutant of an organism of this species.
+    ///
+    /// Every change stays within this species' genes: lengths and colors are
+    /// nudged inside their ranges, termini and new attachments come from the
+    /// allowed lists, vertebrae stay within the count range and only optional
+    /// sockets are emptied. The mutant's seed is derived from the original's
+    /// and `index`, and the result is checked with [`Organism::validate`].
+    pub fn mutate(
+        &self,
+        organism: &Organism,
+        rates: &MutationRates,
+        index: u64,
+    ) -> Result<Organism, OrganismValidationErrors> {
+        let genome_seed = organism.genome_seed.derive("mutant").derive_index(index);
+        let seed = genome_seed.derive("mutation");
+
+        let mut head = organism.head.clone();
+        mutate_cranium(&mut head, self.head(), rates, seed.derive("head"));
+        let mut torso = organism.torso.clone();
+        mutate_torso(&mut torso, self, rates, seed.derive("torso"));
+
+        Organism::validate(
+            UnvalidatedOrganism {
+                genome_seed,
+                symmetry: organism.symmetry,
+                head,
+                torso,
+            },
+            self,
+        )
+    }
+}
+
+impl UnvalidatedSpecies {
+    /// A mutated copy of these genes.
+    ///
+    /// Every range of the bone, tissue and surface genes is widened or
+    /// narrowed around its centre, appendage sockets may start allowing an
+    /// appendage that another socket of the species allows, and socket rules
+    /// may flip `required`. Vertebra and limb segment counts stay as they are.
+    /// The result is not validated; pass it to [`Species::new`].
+    pub fn mutate(&self, rates: &SpeciesMutationRates, seed: GenomeSeed) -> UnvalidatedSpecies {
+        let mut genes = self.clone();
+        let mut rng: GenomeRng = seed.into();
+
+        // Appendages any socket allows, for sockets to borrow from
+        let pool: Vec<AppendageGenes> = genes
+            .torso
+            .spine
+            .appendage_sockets
+            .iter()
+            .flat_map(|rule| rule.allowed.values().cloned())
+            .collect();
+
+        for rule in &mut genes.torso.spine.appendage_sockets {
+            if roll(rates.allowed_appendage, &mut rng) {
+                let candidates: Vec<_> = pool
+                    .iter()
+                    .filter(|genes| rule.allowed.values().all(|own| own.class != genes.class))
+                    .collect();
+                if !candidates.is_empty() {
+                    let genes = candidates[rng.random_range(0..candidates.len())].clone();
+                    let weight = rule.allowed.total_weight() / rule.allowed.len() as f32;
+                    rule.allowed.push(genes, weight);
+                }
+            }
+            if roll(rates.required, &mut rng) {
+                rule.required = !rule.required;
+            }
+            for genes in rule.allowed.values_mut() {
+                resize_bone(&mut genes.limb.segment, rates, &mut rng);
+                resize_integument(&mut genes.integument, rates, &mut rng);
+            }
+        }
+        for rule in &mut genes.torso.spine.feature_sockets {
+            if roll(rates.required, &mut rng) {
+                rule.required = !rule.required;
+            }
+        }
+        for rule in &mut genes.head.sensory_sockets {
+            if roll(rates.required, &mut rng) {
+                rule.required = !rule.required;
+            }
+        }
+        for rule in &mut genes.head.feature_sockets {
+            if roll(rates.required, &mut rng) {
+                rule.required = !rule.required;
+            }
+        }
+
+        resize_bone(&mut genes.head.bone, rates, &mut rng);
+        resize_integument(&mut genes.head.integument, rates, &mut rng);
+        resize_bone(&mut genes.torso.spine.vertebra, rates, &mut rng);
+        resize_tissue(&mut genes.torso.base_tissue, rates, &mut rng);
+        resize_integument(&mut genes.torso.integument, rates, &mut rng);
+
+        genes
+    }
+}
+
+/// Draws organism mutations from one stream
+struct Mutator<'r> {
+    rates: &'r MutationRates,
+    rng: GenomeRng,
+}
+
+impl<'r> Mutator<'r> {
+    fn new(rates: &'r MutationRates, seed: GenomeSeed) -> Self {
+        Self {
+            rates,
+            rng: seed.into(),
+        }
+    }
+
+    fn roll(&mut self, rate: Normalized) -> bool {
+        self.rng.random_bool(rate.value() as f64)
+    }
+
+    /// Move `value` by up to `step` of the range's width, staying inside it
+    fn nudge(&mut self, value: f32, min: f32, max: f32) -> f32 {
+        let (low, high) = (min.min(max), min.max(max));
+        let step = self.rng.random_range(-1.0..=1.0) * self.rates.step.value() * (high - low);
+        (value + step).clamp(low, high)
+    }
+}
+
+fn mutate_cranium(
+    cranium: &mut Cranium,
+    genes: &CraniumGenes,
+    rates: &MutationRates,
+    seed: GenomeSeed,
+) {
+    let mutator = &mut Mutator::new(rates, seed);
+    mutate_bone(&mut cranium.bone, &genes.bone, mutator);
+    mutate_integument(&mut cranium.integument, &genes.integument, mutator);
+
+    for (i, (socket, rule)) in cranium
+        .sensory_sockets
+        .iter_mut()
+        .zip(&genes.sensory_sockets)
+        .enumerate()
+    {
+        let seed = seed.derive("sensory_sockets").derive_index(i as u64);
+        for_each_side(socket, seed, rates, |socket, _, mutator| {
+            if !rule.required && mutator.roll(rates.socket_fill) {
+                toggle_fill(socket, mutator, |rng| Ok(rule.allowed.choose(rng).clone()));
+            }
+        });
+    }
+
+    for (i, (socket, rule)) in cranium
+        .feature_sockets
+        .iter_mut()
+        .zip(&genes.feature_sockets)
+        .enumerate()
+    {
+        let seed = seed.derive("feature_sockets").derive_index(i as u64);
+        for_each_side(socket, seed, rates, |socket, _, mutator| {
+            if !rule.required && mutator.roll(rates.socket_fill) {
+                toggle_fill(socket, mutator, |rng| Ok(rule.allowed.choose(rng).clone()));
+            }
+        });
+    }
+}
+
+fn mutate_torso(torso: &mut Torso, species: &Species, rates: &MutationRates, seed: GenomeSeed) {
+    let genes = species.torso();
+    let mutator = &mut Mutator::new(rates, seed);
+
+    if mutator.roll(rates.vertebra_count) {
+        change_vertebra_count(torso, species, mutator, seed.derive("vertebra_count"));
+    }
+    for vertebra in torso.spine.vertebrae.iter_mut() {
+        mutate_bone(&mut vertebra.bone, &genes.spine.vertebra, mutator);
+    }
+    mutate_integument(&mut torso.integument, &genes.integument, mutator);
+
+    let appendage_seed = seed.derive("appendages");
+    for (i, order) in attachment_order(&torso.spine.appendages)
+        .into_iter()
+        .enumerate()
+    {
+        let attachment = &mut torso.spine.appendages[i];
+        let vertebra = attachment.vertebra_index;
+        let Some(rule) = genes
+            .spine
+            .appendage_sockets
+            .iter()
+            .filter(|rule| rule.vertebra_indices.iter().any(|idx| *idx == vertebra))
+            .nth(order)
+        else {
+            continue;
+        };
+
+        let seed = appendage_seed.derive_index(i as u64);
+        for_each_side(
+            &mut attachment.socket,
+            seed,
+            rates,
+            |socket, side, mutator| {
+                let allowed = rule.allowed_on(side);
+                if !rule.required && mutator.roll(rates.socket_fill) {
+                    let seed = seed.derive("fill");
+                    toggle_fill(socket, mutator, |rng| {
+                        generate_appendage(allowed.choose(rng), seed)
+                    });
+                } else if let Some(appendage) = &mut socket.attachment
+                    && let Some(genes) = allowed
+                        .values()
+                        .find(|genes| genes.class == appendage.class)
+                {
+                    mutate_appendage(appendage, genes, mutator);
+                }
+            },
+        );
+    }
+
+    let feature_seed = seed.derive("features");
+    for (i, order) in attachment_order(&torso.spine.features)
+        .into_iter()
+        .enumerate()
+    {
+        let attachment = &mut torso.spine.features[i];
+        let vertebra = attachment.vertebra_index;
+        let Some(rule) = genes
+            .spine
+            .feature_sockets
+            .iter()
+            .filter(|rule| rule.vertebra_indices.iter().any(|idx| *idx == vertebra))
+            .nth(order)
+        else {
+            continue;
+        };
+
+        let seed = feature_seed.derive_index(i as u64);
+        for_each_side(&mut attachment.socket, seed, rates, |socket, _, mutator| {
+            if !rule.required && mutator.roll(rates.socket_fill) {
+                toggle_fill(socket, mutator, |rng| Ok(rule.allowed.choose(rng).clone()));
+            }
+        });
+    }
+}
+
+/// Add a vertebra, with its sockets filled as generation would, or drop the
+/// last one and everything attached to it
+fn change_vertebra_count(
+    torso: &mut Torso,
+    species: &Species,
+    mutator: &mut Mutator,
+    seed: GenomeSeed,
+) {
+    let genes = &species.torso().spine;
+    let count = torso.spine.vertebrae.len();
+    let can_grow = count < species.max_vertebrae().value() as usize;
+    // Dropping the last vertebra must leave room for every required socket
+    let can_shrink = count > (species.min_vertebrae().value() as usize).max(1)
+        && species
+            .unmet_spine_constraint(Count::new(count as u8 - 1))
+            .is_none();
+
+    let grow = match (can_grow, can_shrink) {
+        (true, true) => mutator.rng.random_bool(0.5),
+        (grow, shrink) if grow || shrink => grow,
+        _ => return,
+    };
+
+    if !grow {
+        torso.spine.vertebrae.pop();
+        let count = torso.spine.vertebrae.len();
+        let fits = |index: VertebraIndex| (index.0 as usize) < count;
+        torso
+            .spine
+            .appendages
+            .retain(|attachment| fits(attachment.vertebra_index));
+        torso
+            .spine
+            .features
+            .retain(|attachment| fits(attachment.vertebra_index));
+        return;
+    }
+
+    let Ok(bone) = generate_bone(&genes.vertebra, seed.derive("vertebra")) else {
+        return;
+    };
+    let vertebra_index = VertebraIndex(count as u8);
+    let on_new_vertebra =
+        |indices: &NonEmpty<VertebraIndex>| indices.iter().any(|idx| *idx == vertebra_index);
+
+    let mut appendages = Vec::new();
+    for (i, rule) in genes.appendage_sockets.iter().enumerate() {
+        if on_new_vertebra(&rule.vertebra_indices) {
+            let Ok(socket) = fill_appendage_socket(
+                rule,
+                species.symmetry(),
+                seed.derive("appendage_sockets").derive_index(i as u64),
+            ) else {
+                return;
+            };
+            appendages.push(SpinalAttachment {
+                vertebra_index,
+                socket,
+            });
+        }
+    }
+
+    let mut features = Vec::new();
+    for (i, rule) in genes.feature_sockets.iter().enumerate() {
+        if on_new_vertebra(&rule.vertebra_indices) {
+            let Ok(socket) = fill_socket(
+                &rule.placement,
+                species.symmetry(),
+                &rule.allowed,
+                rule.required,
+                seed.derive("feature_sockets").derive_index(i as u64),
+                |feature, _| Ok(feature.clone()),
+            ) else {
+                return;
+            };
+            features.push(SpinalAttachment {
+                vertebra_index,
+                socket,
+            });
+        }
+    }
+
+    torso.spine.vertebrae.push(Vertebra { bone });
+    torso.spine.appendages.extend(appendages);
+    torso.spine.features.extend(features);
+}
+
+/// For each attachment, how many earlier attachments share its vertebra.
+/// Generation emits one attachment per matching rule in rule order, so the
+/// `n`-th attachment on a vertebra belongs to the `n`-th rule covering it.
+pub(crate) fn attachment_order<T>(attachments: &[SpinalAttachment<T>]) -> Vec<usize> {
+    attachments
+        .iter()
+        .enumerate()
+        .map(|(i, attachment)| {
+            attachments[..i]
+                .iter()
+                .filter(|earlier| earlier.vertebra_index == attachment.vertebra_index)
+                .count()
+        })
+        .collect()
+}
+
+/// Run `mutate` on each side of a socket, with the side for lateral pairs.
+/// Both sides of a lateral pair, and every fold of a radial socket, draw
+/// from the same stream, so symmetric organisms stay symmetric.
+fn for_each_side<T>(
+    socket: &mut SymmetricSocket<T>,
+    seed: GenomeSeed,
+    rates: &MutationRates,
+    mut mutate: impl FnMut(&mut Socket<T>, Option<Side>, &mut Mutator),
+) {
+    match socket {
+        SymmetricSocket::Medial(socket) => mutate(socket, None, &mut Mutator::new(rates, seed)),
+        SymmetricSocket::Lateral(pair) => {
+            mutate(
+                &mut pair.left,
+                Some(Side::Left),
+                &mut Mutator::new(rates, seed),
+            );
+            mutate(
+                &mut pair.right,
+                Some(Side::Right),
+                &mut Mutator::new(rates, seed),
+            );
+        }
+        SymmetricSocket::Radial(folds) => {
+            for fold in folds {
+                mutate(fold, None, &mut Mutator::new(rates, seed));
+            }
+        }
+    }
+}
+
+/// Empty a filled socket, or fill an empty one with `build`
+fn toggle_fill<T>(
+    socket: &mut Socket<T>,
+    mutator: &mut Mutator,
+    build: impl FnOnce(&mut GenomeRng) -> Result<T, GenerationError>,
+) {
+    socket.attachment = match socket.attachment.take() {
+        Some(_) => None,
+        None => build(&mut mutator.rng).ok(),
+    };
+}
+
+/// A copy of `appendage` with each length and color moved by up to
+/// `variation` of its gene's range, for sides that mirror with variation
+pub(crate) fn vary_appendage(
+    appendage: &Appendage,
+    allowed: &WeightedChoice<AppendageGenes>,
+    variation: Normalized,
+    seed: GenomeSeed,
+) -> Appendage {
+    let (always, never) = (Normalized::new(1.0).unwrap(), Normalized::new(0.0).unwrap());
+    let rates = MutationRates {
+        length: always,
+        color: always,
+        terminus: never,
+        vertebra_count: never,
+        socket_fill: never,
+        step: variation,
+    };
+    let mut varied = appendage.clone();
+    if let Some(genes) = allowed
+        .values()
+        .find(|genes| genes.class == appendage.class)
+    {
+        mutate_appendage(&mut varied, genes, &mut Mutator::new(&rates, seed));
+    }
+    varied
+}
+
+fn mutate_appendage(appendage: &mut Appendage, genes: &AppendageGenes, mutator: &mut Mutator) {
+    mutate_limb(&mut appendage.structure, &genes.limb, mutator);
+    mutate_integument(&mut appendage.integument, &genes.integument, mutator);
+}
+
+fn mutate_limb(limb: &mut LimbStructure, genes: &LimbGenes, mutator: &mut Mutator) {
+    for segment in &mut limb.segments {
+        mutate_bone(&mut segment.bone, &genes.segment, mutator);
+    }
+    if let Some(branching) = &mut limb.branching {
+        mutate_limb(&mut branching.branch, genes, mutator);
+    }
+    if mutator.roll(mutator.rates.terminus) {
+        limb.terminus = genes.allowed_termini.choose(&mut mutator.rng).clone();
+    }
+}
+
+fn mutate_bone(bone: &mut Bone, genes: &BoneGenes, mutator: &mut Mutator) {
+    if mutator.roll(mutator.rates.length) {
+        let range = &genes.length;
+        let length = mutator.nudge(bone.length.value(), range.min.value(), range.max.value());
+        bone.length = Length::new(length).unwrap_or(bone.length);
+    }
+}
+
+fn mutate_integument(integument: &mut Integument, genes: &IntegumentGenes, mutator: &mut Mutator) {
+    if mutator.roll(mutator.rates.color) {
+        let (color, min, max) = (
+            integument.base_color,
+            genes.base_color.min,
+            genes.base_color.max,
+        );
+        integument.base_color = LinearRgba {
+            red: mutator.nudge(color.red, min.red, max.red),
+            green: mutator.nudge(color.green, min.green, max.green),
+            blue: mutator.nudge(color.blue, min.blue, max.blue),
+            alpha: mutator.nudge(color.alpha, min.alpha, max.alpha),
+        };
+    }
+}
+
+pub(crate) fn roll(rate: Normalized, rng: &mut GenomeRng) -> bool {
+    rng.random_bool(rate.value() as f64)
+}
+
+/// Scale the width of a range around its centre by up to `step` either way.
+/// Zero-width ranges can only widen. Changes that would leave the type's
+/// valid values are dropped.
+fn resize(min: f32, max: f32, rates: &SpeciesMutationRates, rng: &mut GenomeRng) -> (f32, f32) {
+    let centre = (min + max) / 2.0;
+    let half = (max - min).abs() / 2.0;
+    let factor = 1.0 + rng.random_range(-1.0..=1.0) * rates.step.value();
+    let half = if half > 0.0 {
+        half * factor
+    } else {
+        centre.abs() * (factor - 1.0).max(0.0)
+    };
+    (centre - half, centre + half)
+}
+
+fn resize_bone(genes: &mut BoneGenes, rates: &SpeciesMutationRates, rng: &mut GenomeRng) {
+    if roll(rates.range, rng) {
+        let range = &mut genes.length;
+        let (min, max) = resize(range.min.value(), range.max.value(), rates, rng);
+        if let (Some(min), Some(max)) = (Length::new(min), Length::new(max)) {
+            *range = ValueRange { min, max };
+        }
+    }
+    resize_tissue(&mut genes.tissue, rates, rng);
+}
+
+fn resize_tissue(
+    genes: &mut TissueEnvelopeGenes,
+    rates: &SpeciesMutationRates,
+    rng: &mut GenomeRng,
+) {
+    if roll(rates.range, rng) {
+        // Point by point, keeping each curve's positions and interpolation
+        let ValueRange { min, max } = &genes.radius_range;
+        let (mut low, mut high) = (min.points().to_vec(), max.points().to_vec());
+        for (low, high) in low.iter_mut().zip(&mut high) {
+            (low.value, high.value) = resize(low.value, high.value, rates, rng);
+        }
+        let radius = |points: Vec<CurvePoint>, like: &Curve| {
+            Curve::new(points).map(|curve| curve.with_interpolation(like.interpolation()))
+        };
+        if low.iter().all(|point| point.value >= 0.0)
+            && let (Some(low), Some(high)) = (radius(low, min), radius(high, max))
+        {
+            genes.radius_range = ValueRange {
+                min: low,
+                max: high,
+            };
+        }
+    }
+    if roll(rates.range, rng) {
+        let range = &mut genes.bulge_count;
+        let (start, end) = resize(
+            range.start().value() as f32,
+            range.end().value() as f32,
+            rates,
+            rng,
+        );
+        let count = |value: f32| Count::new(value.round().clamp(0.0, u8::MAX as f32) as u8);
+        *range = InclusiveRange::new(count(start), count(end));
+    }
+    if roll(rates.range, rng) {
+        let range = &mut genes.bulge_intensity;
+        let (min, max) = resize(range.min.value(), range.max.value(), rates, rng);
+        if let (Some(min), Some(max)) = (MuscleIntensity::new(min), MuscleIntensity::new(max)) {
+            *range = ValueRange { min, max };
+        }
+    }
+}
+
+fn resize_integument(
+    genes: &mut IntegumentGenes,
+    rates: &SpeciesMutationRates,
+    rng: &mut GenomeRng,
+) {
+    if roll(rates.range, rng) {
+        // Channel by channel, dropping the change if any leaves the unit range
+        let ValueRange { min, max } = genes.base_color;
+        let (mut low, mut high) = (min.to_f32_array(), max.to_f32_array());
+        for (low, high) in low.iter_mut().zip(&mut high) {
+            (*low, *high) = resize(*low, *high, rates, rng);
+        }
+        if low
+            .iter()
+            .chain(&high)
+            .all(|channel| (0.0..=1.0).contains(channel))
+        {
+            genes.base_color = ValueRange {
+                min: LinearRgba::from_f32_array(low),
+                max: LinearRgba::from_f32_array(high),
+            };
+        }
+    }
+    if roll(rates.range, rng) {
+        let range = &mut genes.roughness;
+        let (min, max) = resize(range.min.0.value(), range.max.0.value(), rates, rng);
+        if let (Some(min), Some(max)) = (Normalized::new(min), Normalized::new(max)) {
+            *range = ValueRange {
+                min: Roughness(min),
+                max: Roughness(max),
+            };
+        }
+    }
+    if roll(rates.range, rng) {
+        let range = &mut genes.metallic;
+        let (min, max) = resize(range.min.0.value(), range.max.0.value(), rates, rng);
+        if let (Some(min), Some(max)) = (Normalized::new(min), Normalized::new(max)) {
+            *range = ValueRange {
+                min: Metallic(min),
+                max: Metallic(max),
+            };
+        }
+    }
+}
@@ -1,13 +1,26 @@
 use crate::{
-    body::Torso,
+    appendage::{Appendage, LimbStructure},
+    body::{SpinalAttachment, Torso},
     head::Cranium,
+    mutation::attachment_order,
     primitives::*,
-    sockets_symmetry::{BilateralPair, BodySymmetry, SymmetricSocket},
-    species::Species,
-    validation_errors::OrganismValidationError,
+    skeletal::Bone,
+    skeleton::Side,
+    sockets_symmetry::{BodySymmetry, SymmetricSocket},
+    species::{
+        AppendageGenes, BoneGenes, CraniumGenes, IntegumentGenes, LimbGenes, Species,
+        SymmetricPlacement,
+    },
+    surface::Integument,
+    validation_errors::{OrganismValidationError, OrganismValidationErrors},
 };
 
+/// A concrete creature generated from, or validated against, a [`Species`].
+///
+/// Organisms only serialize; saved ones deserialize as an
+/// [`UnvalidatedOrganism`] and go through [`Organism::validate`].
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Organism {
     pub(crate) genome_seed: GenomeSeed,
     pub(crate) symmetry: BodySymmetry,
@@ -31,6 +44,7 @@ impl Organism {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnvalidatedOrganism {
     pub genome_seed: GenomeSeed,
     pub symmetry: BodySymmetry,
@@ -39,61 +53,470 @@ pub struct UnvalidatedOrganism {
 }
 
 impl Organism {
-    /// Validate a manually constructed organism against a species
+    /// Check that a manually constructed organism could have come from
+    /// `species`.
+    ///
+    /// Every part is compared with the gene that produces it: vertebra count,
+    /// bone lengths and colors must lie in their ranges, termini, patterns,
+    /// features and organs must come from the allowed lists, and every socket
+    /// must match a rule, with both sides of lateral pairs and every fold of
+    /// radial sockets checked. Every problem is reported.
     pub fn validate(
         input: UnvalidatedOrganism,
         species: &Species,
-    ) -> Result<Self, OrganismValidationError> {
-        let vertebra_count = input.torso.spine.vertebrae.len();
-
-        // Validate appendage attachment indices
-        for attachment in &input.torso.spine.appendages {
-            if attachment.vertebra_index.0 as usize >= vertebra_count {
-                return Err(OrganismValidationError::VertebraIndexOutOfBounds {
-                    index: attachment.vertebra_index,
-                    vertebra_count,
+    ) -> Result<Self, OrganismValidationErrors> {
+        let mut checker = Checker {
+            fold_count: species.symmetry().fold_count().map(Count::value),
+            ..Checker::default()
+        };
+        if input.symmetry != species.symmetry() {
+            checker
+                .errors
+                .push(OrganismValidationError::SymmetryMismatch {
+                    expected: species.symmetry(),
+                    found: input.symmetry,
+                });
+        }
+        checker.cranium("head", &input.head, species.head());
+        checker.torso("torso", &input.torso, species);
+        if !checker.errors.is_empty() {
+            return Err(OrganismValidationErrors(checker.errors));
+        }
+
+        Ok(Self {
+            genome_seed: input.genome_seed,
+            symmetry: input.symmetry,
+            head: input.head,
+            torso: input.torso,
+        })
+    }
+}
+
+impl From<Organism> for UnvalidatedOrganism {
+    fn from(organism: Organism) -> Self {
+        Self {
+            genome_seed: organism.genome_seed,
+            symmetry: organism.symmetry,
+            head: organism.head,
+            torso: organism.torso,
+        }
+    }
+}
+
+/// Sampled values may land a rounding error outside their range
+const RANGE_TOLERANCE: f32 = 1e-5;
+
+/// Walks an organism alongside its species' genes, collecting every problem
+/// with its path
+#[derive(Default)]
+struct Checker {
+    /// Folds every radial socket must have
+    fold_count: Option<u8>,
+    errors: Vec<OrganismValidationError>,
+}
+
+impl Checker {
+    fn cranium(&mut self, path: &str, head: &Cranium, genes: &CraniumGenes) {
+        self.bone(&format!("{path}.bone"), &head.bone, &genes.bone);
+
+        self.head_sockets(
+            &format!("{path}.sensory_sockets"),
+            &head.sensory_sockets,
+            &genes.sensory_sockets,
+            |rule| (&rule.placement, &rule.allowed, rule.required),
+        );
+        self.head_sockets(
+            &format!("{path}.feature_sockets"),
+            &head.feature_sockets,
+            &genes.feature_sockets,
+            |rule| (&rule.placement, &rule.allowed, rule.required),
+        );
+
+        let socket_path = format!("{path}.mandible_socket");
+        match (&head.mandible_socket, &genes.mandible) {
+            (Some(socket), Some(allowed)) => match &socket.attachment {
+                Some(mandible) => self.allowed(&socket_path, mandible, allowed),
+                None => {
+                    self.push(OrganismValidationError::RequiredSocketEmpty { path: socket_path })
+                }
+            },
+            (Some(_), None) => {
+                self.push(OrganismValidationError::UnexpectedSocket { path: socket_path })
+            }
+            (None, Some(_)) => {
+                self.push(OrganismValidationError::RequiredSocketEmpty { path: socket_path })
+            }
+            (None, None) => {}
+        }
+
+        self.integument(
+            &format!("{path}.integument"),
+            &head.integument,
+            &genes.integument,
+        );
+    }
+
+    /// Head sockets pair with the species' rules by index
+    fn head_sockets<T: PartialEq, R>(
+        &mut self,
+        path: &str,
+        sockets: &[SymmetricSocket<T>],
+        rules: &[R],
+        rule: impl Fn(&R) -> (&SymmetricPlacement, &WeightedChoice<T>, bool),
+    ) {
+        for (i, socket) in sockets.iter().enumerate() {
+            let path = format!("{path}[{i}]");
+            match rules.get(i).map(&rule) {
+                Some((placement, allowed, required)) => self.socket(
+                    &path,
+                    socket,
+                    placement,
+                    required,
+                    |checker, path, _, value| checker.allowed(path, value, allowed),
+                ),
+                None => self.push(OrganismValidationError::UnexpectedSocket { path }),
+            }
+        }
+        for (i, missing) in rules.iter().enumerate().skip(sockets.len()) {
+            if rule(missing).2 {
+                self.push(OrganismValidationError::RequiredSocketEmpty {
+                    path: format!("{path}[{i}]"),
                 });
             }
         }
+    }
+
+    fn torso(&mut self, path: &str, torso: &Torso, species: &Species) {
+        let genes = species.torso();
+        let spine = &torso.spine;
+        let count = spine.vertebrae.len();
+        if !(species.min_vertebrae().value() as usize..=species.max_vertebrae().value() as usize)
+            .contains(&count)
+        {
+            self.push(OrganismValidationError::VertebraCountOutOfRange {
+                count,
+                min: species.min_vertebrae(),
+                max: species.max_vertebrae(),
+            });
+        }
+        for (i, vertebra) in spine.vertebrae.iter().enumerate() {
+            self.bone(
+                &format!("{path}.spine.vertebrae[{i}].bone"),
+                &vertebra.bone,
+                &genes.spine.vertebra,
+            );
+        }
+
+        let rules: Vec<_> = genes
+            .spine
+            .appendage_sockets
+            .iter()
+            .map(|rule| (&rule.vertebra_indices, &rule.placement, rule.required))
+            .collect();
+        self.spinal(
+            &format!("{path}.spine.appendages"),
+            &format!("{path}.spine.appendage_sockets"),
+            &spine.appendages,
+            &rules,
+            count,
+            |checker, path, rule, side, appendage| {
+                checker.appendage(
+                    path,
+                    appendage,
+                    genes.spine.appendage_sockets[rule].allowed_on(side),
+                )
+            },
+        );
 
-        // Validate feature attachment indices
-        for attachment in &input.torso.spine.features {
-            if attachment.vertebra_index.0 as usize >= vertebra_count {
-                return Err(OrganismValidationError::VertebraIndexOutOfBounds {
-                    index: attachment.vertebra_index,
+        let rules: Vec<_> = genes
+            .spine
+            .feature_sockets
+            .iter()
+            .map(|rule| (&rule.vertebra_indices, &rule.placement, rule.required))
+            .collect();
+        self.spinal(
+            &format!("{path}.spine.features"),
+            &format!("{path}.spine.feature_sockets"),
+            &spine.features,
+            &rules,
+            count,
+            |checker, path, rule, _, feature| {
+                checker.allowed(path, feature, &genes.spine.feature_sockets[rule].allowed)
+            },
+        );
+
+        self.integument(
+            &format!("{path}.integument"),
+            &torso.integument,
+            &genes.integument,
+        );
+    }
+
+    /// Spinal attachments pair with rules the way generation emits them: the
+    /// `n`-th attachment on a vertebra belongs to the `n`-th rule covering it
+    fn spinal<T>(
+        &mut self,
+        path: &str,
+        rules_path: &str,
+        attachments: &[SpinalAttachment<T>],
+        rules: &[(&NonEmpty<VertebraIndex>, &SymmetricPlacement, bool)],
+        vertebra_count: usize,
+        mut check: impl FnMut(&mut Self, &str, usize, Option<Side>, &T),
+    ) {
+        let mut matched = Vec::new();
+        for (i, order) in attachment_order(attachments).into_iter().enumerate() {
+            let attachment = &attachments[i];
+            let path = format!("{path}[{i}]");
+            let index = attachment.vertebra_index;
+            if index.0 as usize >= vertebra_count {
+                self.push(OrganismValidationError::VertebraIndexOutOfBounds {
+                    path,
+                    index,
                     vertebra_count,
                 });
+                continue;
+            }
+
+            let Some((rule, (_, placement, required))) = rules
+                .iter()
+                .enumerate()
+                .filter(|(_, (indices, _, _))| indices.iter().any(|idx| *idx == index))
+                .nth(order)
+            else {
+                self.push(OrganismValidationError::UnexpectedSocket { path });
+                continue;
+            };
+            matched.push((rule, index));
+            self.socket(
+                &path,
+                &attachment.socket,
+                placement,
+                *required,
+                |checker, path, side, value| check(checker, path, rule, side, value),
+            );
+        }
+
+        for (rule, (indices, _, required)) in rules.iter().enumerate() {
+            if !*required {
+                continue;
+            }
+            let mut in_range = indices
+                .iter()
+                .enumerate()
+                .filter(|(_, idx)| (idx.0 as usize) < vertebra_count)
+                .peekable();
+            if in_range.peek().is_none() {
+                self.push(OrganismValidationError::RequiredSocketEmpty {
+                    path: format!("{rules_path}[{rule}].vertebra_indices"),
+                });
+            }
+            for (k, idx) in in_range {
+                if !matched.contains(&(rule, *idx)) {
+                    self.push(OrganismValidationError::RequiredSocketEmpty {
+                        path: format!("{rules_path}[{rule}].vertebra_indices[{k}]"),
+                    });
+                }
             }
         }
+    }
 
-        // Validate required sockets are filled
-        for socket_rule in &species.torso().spine.appendage_sockets {
-            if socket_rule.required {
-                // Check that at least one valid vertebra has an attachment
-                let has_attachment = socket_rule.vertebra_indices.iter().any(|idx| {
-                    input.torso.spine.appendages.iter().any(|a| {
-                        a.vertebra_index == *idx
-                            && matches!(&a.socket,
-                                SymmetricSocket::Medial(s) |
-                                SymmetricSocket::Lateral(BilateralPair { left: s, .. })
-                                if s.attachment.is_some()
-                            )
-                    })
+    /// Check each side or fold of a socket against its rule's placement,
+    /// passing `check` the side of lateral pairs
+    fn socket<T>(
+        &mut self,
+        path: &str,
+        socket: &SymmetricSocket<T>,
+        placement: &SymmetricPlacement,
+        required: bool,
+        mut check: impl FnMut(&mut Self, &str, Option<Side>, &T),
+    ) {
+        let sides = match (socket, placement) {
+            (SymmetricSocket::Medial(socket), SymmetricPlacement::Medial(_)) => {
+                vec![(path.to_string(), None, socket)]
+            }
+            (SymmetricSocket::Lateral(pair), SymmetricPlacement::Lateral { .. }) => vec![
+                (format!("{path}.left"), Some(Side::Left), &pair.left),
+                (format!("{path}.right"), Some(Side::Right), &pair.right),
+            ],
+            (SymmetricSocket::Radial(folds), SymmetricPlacement::Radial { .. }) => {
+                let expected = self.fold_count.unwrap_or_default() as usize;
+                if folds.len() != expected {
+                    self.push(OrganismValidationError::FoldCountMismatch {
+                        path: path.to_string(),
+                        expected,
+                        found: folds.len(),
+                    });
+                }
+                folds
+                    .iter()
+                    .enumerate()
+                    .map(|(i, fold)| (format!("{path}.folds[{i}]"), None, fold))
+                    .collect()
+            }
+            _ => {
+                self.push(OrganismValidationError::PlacementMismatch {
+                    path: path.to_string(),
                 });
+                return;
+            }
+        };
+
+        for (path, side, socket) in sides {
+            match &socket.attachment {
+                Some(value) => check(self, &path, side, value),
+                None if required => {
+                    self.push(OrganismValidationError::RequiredSocketEmpty { path })
+                }
+                None => {}
+            }
+        }
+    }
 
-                if !has_attachment {
-                    return Err(OrganismValidationError::RequiredSocketEmpty {
-                        vertebra_index: *socket_rule.vertebra_indices.first(),
+    /// Accept the appendage if any allowed appendage of its class could have
+    /// produced it; otherwise report against the first of that class
+    fn appendage(
+        &mut self,
+        path: &str,
+        appendage: &Appendage,
+        allowed: &WeightedChoice<AppendageGenes>,
+    ) {
+        let mut candidates = allowed
+            .values()
+            .filter(|genes| genes.class == appendage.class)
+            .map(|genes| {
+                let mut checker = Checker::default();
+                checker.limb(
+                    &format!("{path}.structure"),
+                    &appendage.structure,
+                    &genes.limb,
+                    true,
+                );
+                if appendage.patagium.is_some() && genes.patagium_probability.value() == 0.0 {
+                    checker.push(OrganismValidationError::NotAllowed {
+                        path: format!("{path}.patagium"),
                     });
                 }
+                checker.integument(
+                    &format!("{path}.integument"),
+                    &appendage.integument,
+                    &genes.integument,
+                );
+                checker.errors
+            })
+            .peekable();
+
+        let Some(first) = candidates.peek().cloned() else {
+            self.push(OrganismValidationError::AppendageClassMismatch {
+                path: format!("{path}.class"),
+                found: appendage.class,
+            });
+            return;
+        };
+        if !candidates.any(|errors| errors.is_empty()) {
+            self.errors.extend(first);
+        }
+    }
+
+    fn limb(&mut self, path: &str, limb: &LimbStructure, genes: &LimbGenes, may_branch: bool) {
+        let count = &genes.segment_count;
+        if !(count.start().value() as usize..=count.end().value() as usize)
+            .contains(&limb.segments.len())
+        {
+            self.push(OrganismValidationError::OutOfRange {
+                path: format!("{path}.segments"),
+            });
+        }
+        for (i, segment) in limb.segments.iter().enumerate() {
+            self.bone(
+                &format!("{path}.segments[{i}].bone"),
+                &segment.bone,
+                &genes.segment,
+            );
+        }
+        self.allowed(
+            &format!("{path}.terminus"),
+            &limb.terminus,
+            &genes.allowed_termini,
+        );
+
+        if let Some(branching) = &limb.branching {
+            let path = format!("{path}.branching");
+            // Branches never branch again
+            if !may_branch || genes.branching_probability.value() == 0.0 {
+                self.push(OrganismValidationError::NotAllowed { path });
+            } else {
+                self.limb(&format!("{path}.branch"), &branching.branch, genes, false);
             }
         }
+    }
 
-        Ok(Self {
-            genome_seed: input.genome_seed,
-            symmetry: input.symmetry,
-            head: input.head,
-            torso: input.torso,
-        })
+    fn bone(&mut self, path: &str, bone: &Bone, genes: &BoneGenes) {
+        let range = &genes.length;
+        self.range(
+            &format!("{path}.length"),
+            &[(bone.length.value(), range.min.value(), range.max.value())],
+        );
+    }
+
+    fn integument(&mut self, path: &str, integument: &Integument, genes: &IntegumentGenes) {
+        let (color, min, max) = (
+            integument.base_color,
+            genes.base_color.min,
+            genes.base_color.max,
+        );
+        self.range(
+            &format!("{path}.base_color"),
+            &[
+                (color.red, min.red, max.red),
+                (color.green, min.green, max.green),
+                (color.blue, min.blue, max.blue),
+                (color.alpha, min.alpha, max.alpha),
+            ],
+        );
+        self.allowed(
+            &format!("{path}.pattern"),
+            &integument.pattern,
+            &genes.allowed_patterns,
+        );
+        self.range(
+            &format!("{path}.roughness"),
+            &[(
+                integument.roughness.0.value(),
+                genes.roughness.min.0.value(),
+                genes.roughness.max.0.value(),
+            )],
+        );
+        self.range(
+            &format!("{path}.metallic"),
+            &[(
+                integument.metallic.0.value(),
+                genes.metallic.min.0.value(),
+                genes.metallic.max.0.value(),
+            )],
+        );
+    }
+
+    fn allowed<T: PartialEq>(&mut self, path: &str, value: &T, allowed: &WeightedChoice<T>) {
+        if !allowed.values().any(|candidate| candidate == value) {
+            self.push(OrganismValidationError::NotAllowed {
+                path: path.to_string(),
+            });
+        }
+    }
+
+    /// Report `path` once if any `(value, min, max)` falls outside its bounds
+    fn range(&mut self, path: &str, values: &[(f32, f32, f32)]) {
+        let outside = values.iter().any(|&(value, min, max)| {
+            !(min - RANGE_TOLERANCE..=max + RANGE_TOLERANCE).contains(&value)
+        });
+        if outside {
+            self.push(OrganismValidationError::OutOfRange {
+                path: path.to_string(),
+            });
+        }
+    }
+
+    fn push(&mut self, error: OrganismValidationError) {
+        self.errors.push(error);
     }
 }
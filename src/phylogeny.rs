@@ -0,0 +1,483 @@
+use std::fmt::{Debug, Write};
+
+use bevy::prelude::Resource;
+
+use crate::{
+    primitives::WeightedChoice,
+    species::{
+        AppendageGenes, BoneGenes, CraniumGenes, FeatureSocketRule, IntegumentGenes, Species,
+        TissueEnvelopeGenes, TorsoGenes,
+    },
+    validation_errors::PhylogenyError,
+};
+
+/// Index of a species recorded in a [`Phylogeny`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpeciesId(usize);
+
+/// One gene that differs between a species and its parent
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneChange {
+    /// Path of the gene, such as `torso.spine.vertebra.length`
+    pub path: String,
+    /// The parent's value, or `None` if the daughter added it
+    pub before: Option<String>,
+    /// The daughter's value, or `None` if the daughter removed it
+    pub after: Option<String>,
+}
+
+/// A species in the tree and the branch that led to it
+#[derive(Clone, Debug)]
+pub struct PhylogenyNode {
+    pub species: Species,
+    pub parent: Option<SpeciesId>,
+    pub children: Vec<SpeciesId>,
+    /// Generation at which the species diverged from its parent, or first
+    /// appeared for roots
+    pub generation: u64,
+    /// How the species' genes differ from its parent's
+    pub changes: Vec<GeneChange>,
+}
+
+/// The evolutionary tree of every species recorded so far.
+///
+/// Species are linked through [`Species::parent`], so a daughter can only be
+/// recorded after its parent. Species without a parent start new trees.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct Phylogeny {
+    nodes: Vec<PhylogenyNode>,
+}
+
+impl Phylogeny {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a species that appeared at `generation`
+    pub fn record(
+        &mut self,
+        species: Species,
+        generation: u64,
+    ) -> Result<SpeciesId, PhylogenyError> {
+        if self.find(species.name()).is_some() {
+            return Err(PhylogenyError::DuplicateSpecies {
+                name: species.name().to_string(),
+            });
+        }
+
+        let parent = match species.parent() {
+            Some(name) => Some(
+                self.find(name)
+                    .ok_or_else(|| PhylogenyError::UnknownParent {
+                        name: name.to_string(),
+                    })?,
+            ),
+            None => None,
+        };
+        let changes = parent
+            .map(|parent| gene_changes(&self.nodes[parent.0].species, &species))
+            .unwrap_or_default();
+
+        let id = SpeciesId(self.nodes.len());
+        if let Some(parent) = parent {
+            self.nodes[parent.0].children.push(id);
+        }
+        self.nodes.push(PhylogenyNode {
+            species,
+            parent,
+            children: Vec::new(),
+            generation,
+            changes,
+        });
+        Ok(id)
+    }
+
+    pub fn get(&self, id: SpeciesId) -> &PhylogenyNode {
+        &self.nodes[id.0]
+    }
+
+    pub fn find(&self, name: &str) -> Option<SpeciesId> {
+        self.nodes
+            .iter()
+            .position(|node| node.species.name() == name)
+            .map(SpeciesId)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SpeciesId, &PhylogenyNode)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (SpeciesId(i), node))
+    }
+
+    /// Species without a recorded parent
+    pub fn roots(&self) -> impl Iterator<Item = SpeciesId> + '_ {
+        self.iter()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(id, _)| id)
+    }
+
+    /// The species itself, then its parent, and so on up to its root
+    pub fn lineage(&self, id: SpeciesId) -> impl Iterator<Item = SpeciesId> + '_ {
+        std::iter::successors(Some(id), |id| self.nodes[id.0].parent)
+    }
+
+    /// The most recent species both descend from, counting each as its own
+    /// ancestor. `None` if they are in different trees.
+    pub fn common_ancestor(&self, a: SpeciesId, b: SpeciesId) -> Option<SpeciesId> {
+        let ancestors: Vec<_> = self.lineage(a).collect();
+        self.lineage(b).find(|id| ancestors.contains(id))
+    }
+
+    /// Number of branches on the path between two species
+    pub fn distance(&self, a: SpeciesId, b: SpeciesId) -> Option<usize> {
+        let ancestor = self.common_ancestor(a, b)?;
+        let steps = |id| self.lineage(id).position(|id| id == ancestor);
+        Some(steps(a)? + steps(b)?)
+    }
+
+    /// Generations elapsed along the path between two species
+    pub fn generations_apart(&self, a: SpeciesId, b: SpeciesId) -> Option<u64> {
+        let ancestor = self.get(self.common_ancestor(a, b)?).generation;
+        Some(
+            self.get(a).generation.saturating_sub(ancestor)
+                + self.get(b).generation.saturating_sub(ancestor),
+        )
+    }
+
+    /// Every species descended from `id`, depth first, not including `id`
+    pub fn descendants(&self, id: SpeciesId) -> Vec<SpeciesId> {
+        let mut descendants = Vec::new();
+        let mut stack: Vec<_> = self.nodes[id.0].children.iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            descendants.push(id);
+            stack.extend(self.nodes[id.0].children.iter().rev());
+        }
+        descendants
+    }
+
+    /// The tree in Newick format, one line per root, with branch lengths in
+    /// generations
+    pub fn to_newick(&self) -> String {
+        let mut out = String::new();
+        for root in self.roots() {
+            self.write_newick(&mut out, root);
+            out.push_str(";\n");
+        }
+        out
+    }
+
+    fn write_newick(&self, out: &mut String, id: SpeciesId) {
+        let node = &self.nodes[id.0];
+        if !node.children.is_empty() {
+            out.push('(');
+            for (i, child) in node.children.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                self.write_newick(out, *child);
+            }
+            out.push(')');
+        }
+        out.push_str(&newick_label(node.species.name()));
+        if let Some(parent) = node.parent {
+            let length = node
+                .generation
+                .saturating_sub(self.nodes[parent.0].generation);
+            write!(out, ":{length}").unwrap();
+        }
+    }
+}
+
+/// Quote labels that contain Newick punctuation or whitespace
+fn newick_label(name: &str) -> String {
+    let plain = !name.is_empty()
+        && !name
+            .chars()
+            .any(|c| c.is_whitespace() || "()[]':;,".contains(c));
+    if plain {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\'', "''"))
+    }
+}
+
+/// Genes that differ between a parent and a daughter species
+fn gene_changes(parent: &Species, daughter: &Species) -> Vec<GeneChange> {
+    let mut diff = GeneDiff::default();
+    diff.value("symmetry", &parent.symmetry(), &daughter.symmetry());
+    diff.cranium("head", parent.head(), daughter.head());
+    diff.torso("torso", parent.torso(), daughter.torso());
+    diff.changes
+}
+
+/// Walks two gene trees field by field, recording each gene whose values
+/// differ under its own path
+#[derive(Default)]
+struct GeneDiff {
+    changes: Vec<GeneChange>,
+}
+
+impl GeneDiff {
+    fn value<T: PartialEq + Debug>(&mut self, path: &str, before: &T, after: &T) {
+        if before != after {
+            self.record(path.to_string(), Some(before), Some(after));
+        }
+    }
+
+    fn record<T: Debug>(&mut self, path: String, before: Option<&T>, after: Option<&T>) {
+        self.changes.push(GeneChange {
+            path,
+            before: before.map(|value| format!("{value:?}")),
+            after: after.map(|value| format!("{value:?}")),
+        });
+    }
+
+    /// Compare entries at the same index with `compare`; entries only one
+    /// list has were added or removed
+    fn list<T: Debug>(
+        &mut self,
+        path: &str,
+        before: &[T],
+        after: &[T],
+        compare: impl Fn(&mut Self, &str, &T, &T),
+    ) {
+        for i in 0..before.len().max(after.len()) {
+            let path = format!("{path}[{i}]");
+            match (before.get(i), after.get(i)) {
+                (Some(before), Some(after)) => compare(self, &path, before, after),
+                (before, after) => self.record(path, before, after),
+            }
+        }
+    }
+
+    fn choice<T: Debug>(
+        &mut self,
+        path: &str,
+        before: &WeightedChoice<T>,
+        after: &WeightedChoice<T>,
+        compare: impl Fn(&mut Self, &str, &T, &T),
+    ) {
+        let before: Vec<_> = before.iter().collect();
+        let after: Vec<_> = after.iter().collect();
+        self.list(path, &before, &after, |diff, path, before, after| {
+            diff.value(&format!("{path}.weight"), &before.weight, &after.weight);
+            compare(diff, path, &before.value, &after.value);
+        });
+    }
+
+    fn cranium(&mut self, path: &str, before: &CraniumGenes, after: &CraniumGenes) {
+        self.bone(&format!("{path}.bone"), &before.bone, &after.bone);
+        self.list(
+            &format!("{path}.sensory_sockets"),
+            &before.sensory_sockets,
+            &after.sensory_sockets,
+            |diff, path, before, after| {
+                diff.value(
+                    &format!("{path}.placement"),
+                    &before.placement,
+                    &after.placement,
+                );
+                diff.choice(
+                    &format!("{path}.allowed"),
+                    &before.allowed,
+                    &after.allowed,
+                    Self::value,
+                );
+                diff.value(
+                    &format!("{path}.required"),
+                    &before.required,
+                    &after.required,
+                );
+            },
+        );
+        self.value(
+            &format!("{path}.mandible"),
+            &before.mandible,
+            &after.mandible,
+        );
+        self.list(
+            &format!("{path}.feature_sockets"),
+            &before.feature_sockets,
+            &after.feature_sockets,
+            Self::feature_socket,
+        );
+        self.integument(
+            &format!("{path}.integument"),
+            &before.integument,
+            &after.integument,
+        );
+    }
+
+    fn torso(&mut self, path: &str, before: &TorsoGenes, after: &TorsoGenes) {
+        let (a, b) = (&before.spine, &after.spine);
+        self.value(
+            &format!("{path}.spine.vertebra_count"),
+            &a.vertebra_count,
+            &b.vertebra_count,
+        );
+        self.bone(&format!("{path}.spine.vertebra"), &a.vertebra, &b.vertebra);
+        self.list(
+            &format!("{path}.spine.appendage_sockets"),
+            &a.appendage_sockets,
+            &b.appendage_sockets,
+            |diff, path, before, after| {
+                diff.value(
+                    &format!("{path}.vertebra_indices"),
+                    &before.vertebra_indices,
+                    &after.vertebra_indices,
+                );
+                diff.value(
+                    &format!("{path}.placement"),
+                    &before.placement,
+                    &after.placement,
+                );
+                diff.choice(
+                    &format!("{path}.allowed"),
+                    &before.allowed,
+                    &after.allowed,
+                    Self::appendage,
+                );
+                diff.value(
+                    &format!("{path}.required"),
+                    &before.required,
+                    &after.required,
+                );
+                diff.value(&format!("{path}.sides"), &before.sides, &after.sides);
+            },
+        );
+        self.list(
+            &format!("{path}.spine.feature_sockets"),
+            &a.feature_sockets,
+            &b.feature_sockets,
+            Self::feature_socket,
+        );
+        self.tissue(
+            &format!("{path}.base_tissue"),
+            &before.base_tissue,
+            &after.base_tissue,
+        );
+        self.integument(
+            &format!("{path}.integument"),
+            &before.integument,
+            &after.integument,
+        );
+    }
+
+    fn feature_socket(
+        &mut self,
+        path: &str,
+        before: &FeatureSocketRule,
+        after: &FeatureSocketRule,
+    ) {
+        self.value(
+            &format!("{path}.vertebra_indices"),
+            &before.vertebra_indices,
+            &after.vertebra_indices,
+        );
+        self.value(
+            &format!("{path}.placement"),
+            &before.placement,
+            &after.placement,
+        );
+        self.choice(
+            &format!("{path}.allowed"),
+            &before.allowed,
+            &after.allowed,
+            Self::value,
+        );
+        self.value(
+            &format!("{path}.required"),
+            &before.required,
+            &after.required,
+        );
+    }
+
+    fn appendage(&mut self, path: &str, before: &AppendageGenes, after: &AppendageGenes) {
+        self.value(&format!("{path}.class"), &before.class, &after.class);
+        let (a, b) = (&before.limb, &after.limb);
+        self.value(
+            &format!("{path}.limb.segment_count"),
+            &a.segment_count,
+            &b.segment_count,
+        );
+        self.bone(&format!("{path}.limb.segment"), &a.segment, &b.segment);
+        self.choice(
+            &format!("{path}.limb.allowed_termini"),
+            &a.allowed_termini,
+            &b.allowed_termini,
+            Self::value,
+        );
+        self.value(
+            &format!("{path}.limb.branching_probability"),
+            &a.branching_probability,
+            &b.branching_probability,
+        );
+        self.value(
+            &format!("{path}.patagium_probability"),
+            &before.patagium_probability,
+            &after.patagium_probability,
+        );
+        self.integument(
+            &format!("{path}.integument"),
+            &before.integument,
+            &after.integument,
+        );
+    }
+
+    fn bone(&mut self, path: &str, before: &BoneGenes, after: &BoneGenes) {
+        self.value(&format!("{path}.length"), &before.length, &after.length);
+        self.tissue(&format!("{path}.tissue"), &before.tissue, &after.tissue);
+    }
+
+    fn tissue(&mut self, path: &str, before: &TissueEnvelopeGenes, after: &TissueEnvelopeGenes) {
+        self.value(&format!("{path}.profile"), &before.profile, &after.profile);
+        self.value(
+            &format!("{path}.radius_range"),
+            &before.radius_range,
+            &after.radius_range,
+        );
+        self.value(
+            &format!("{path}.bulge_count"),
+            &before.bulge_count,
+            &after.bulge_count,
+        );
+        self.value(
+            &format!("{path}.bulge_intensity"),
+            &before.bulge_intensity,
+            &after.bulge_intensity,
+        );
+    }
+
+    fn integument(&mut self, path: &str, before: &IntegumentGenes, after: &IntegumentGenes) {
+        self.value(
+            &format!("{path}.base_color"),
+            &before.base_color,
+            &after.base_color,
+        );
+        self.choice(
+            &format!("{path}.allowed_patterns"),
+            &before.allowed_patterns,
+            &after.allowed_patterns,
+            Self::value,
+        );
+        self.value(
+            &format!("{path}.roughness"),
+            &before.roughness,
+            &after.roughness,
+        );
+        self.value(
+            &format!("{path}.metallic"),
+            &before.metallic,
+            &after.metallic,
+        );
+    }
+}
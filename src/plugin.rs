@@ -0,0 +1,140 @@
+use bevy::{
+    mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+    prelude::*,
+};
+
+use crate::{
+    asset::{GeneratedOrganism, SpeciesAssetPlugin},
+    mesh::{TissueMeshBuilder, TissueMeshConfig},
+    primitives::Length,
+    skeleton::{BoneId, GeneratedSkeleton, SkeletonConfig, SkeletonGenerator, SkeletonNode},
+};
+
+/// Generates organisms for [`SpawnOrganism`](crate::asset::SpawnOrganism)
+/// entities and builds their skeletons as entity hierarchies.
+///
+/// Each bone becomes a child entity with a [`Name`] from [`BoneId::name`], a
+/// [`Transform`] from its rest pose and a [`SkeletonBone`]. When [`Mesh`]
+/// assets are available, the organism entity also gets a [`Mesh3d`] and a
+/// [`SkinnedMesh`] bound to those bones, so posing the bones deforms the body.
+/// Skeletons and meshes are rebuilt whenever the organism is regenerated.
+#[derive(Default)]
+pub struct SpeciationPlugin {
+    pub skeleton: SkeletonConfig,
+    pub mesh: TissueMeshConfig,
+}
+
+impl Plugin for SpeciationPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<SpeciesAssetPlugin>() {
+            app.add_plugins(SpeciesAssetPlugin);
+        }
+
+        app.insert_resource(self.skeleton.clone())
+            .insert_resource(self.mesh.clone())
+            .add_systems(
+                Update,
+                (
+                    spawn_skeletons.after(crate::asset::generate_organisms),
+                    skin_organisms.after(spawn_skeletons).run_if(
+                        resource_exists::<Assets<Mesh>>
+                            .and(resource_exists::<Assets<SkinnedMeshInverseBindposes>>),
+                    ),
+                ),
+            );
+    }
+}
+
+/// A bone entity created from a [`SkeletonNode`]
+#[derive(Component, Clone, Debug)]
+pub struct SkeletonBone {
+    pub id: BoneId,
+    pub length: Length,
+}
+
+/// The skeleton of an organism entity and the entities built for it
+#[derive(Component, Clone, Debug)]
+pub struct OrganismSkeleton {
+    pub skeleton: GeneratedSkeleton,
+    /// Bone entities in [`GeneratedSkeleton::iter`] order; the first is the root
+    pub bones: Vec<Entity>,
+}
+
+impl OrganismSkeleton {
+    pub fn bone_entity(&self, id: &BoneId) -> Option<Entity> {
+        self.skeleton
+            .iter()
+            .position(|node| &node.id == id)
+            .map(|index| self.bones[index])
+    }
+}
+
+fn spawn_skeletons(
+    mut commands: Commands,
+    config: Res<SkeletonConfig>,
+    organisms: Query<
+        (Entity, &GeneratedOrganism, Option<&OrganismSkeleton>),
+        Changed<GeneratedOrganism>,
+    >,
+) {
+    let generator = SkeletonGenerator::new(config.clone());
+
+    for (entity, organism, previous) in &organisms {
+        if let Some(root) = previous.and_then(|previous| previous.bones.first()) {
+            commands.entity(*root).despawn();
+        }
+
+        let skeleton = generator.generate(organism);
+        let mut bones = Vec::with_capacity(skeleton.bone_count());
+        spawn_bone(&mut commands, entity, &skeleton.root, &mut bones);
+
+        commands
+            .entity(entity)
+            .insert(OrganismSkeleton { skeleton, bones });
+    }
+}
+
+fn spawn_bone(
+    commands: &mut Commands,
+    parent: Entity,
+    node: &SkeletonNode,
+    bones: &mut Vec<Entity>,
+) {
+    let entity = commands
+        .spawn((
+            Name::new(node.id.name()),
+            Transform::from_translation(node.rest.translation).with_rotation(node.rest.rotation),
+            SkeletonBone {
+                id: node.id.clone(),
+                length: node.length,
+            },
+            ChildOf(parent),
+        ))
+        .id();
+    bones.push(entity);
+
+    for child in &node.children {
+        spawn_bone(commands, entity, child, bones);
+    }
+}
+
+fn skin_organisms(
+    mut commands: Commands,
+    config: Res<TissueMeshConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut bindposes: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+    organisms: Query<(Entity, &OrganismSkeleton), Changed<OrganismSkeleton>>,
+) {
+    let builder = TissueMeshBuilder::new(config.clone());
+
+    for (entity, skeleton) in &organisms {
+        let skinned = builder.build_skinned(&skeleton.skeleton);
+        commands.entity(entity).insert((
+            Mesh3d(meshes.add(skinned.mesh)),
+            SkinnedMesh {
+                inverse_bindposes: bindposes.add(skinned.inverse_bindposes),
+                joints: skeleton.bones.clone(),
+            },
+        ));
+    }
+}
@@ -0,0 +1,379 @@
+use bevy::prelude::Resource;
+use rand::Rng;
+
+use crate::{
+    crossover::CrossoverPolicy,
+    distance::DistanceWeights,
+    mutation::MutationRates,
+    organism::Organism,
+    phylogeny::{Phylogeny, SpeciesId},
+    primitives::*,
+    speciation::DivergenceConfig,
+    species::Species,
+    validation_errors::PopulationError,
+};
+
+/// How parents are picked from a scored generation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Selection {
+    /// The fittest of `size` organisms drawn at random
+    Tournament { size: usize },
+    /// Chance proportional to fitness above the generation's worst
+    Roulette,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self::Tournament { size: 3 }
+    }
+}
+
+/// When organisms that drift from their species found a new one
+#[derive(Clone, Debug)]
+pub struct SpeciationConfig {
+    /// Distance from its species' founder past which an organism has diverged
+    pub threshold: f32,
+    pub weights: DistanceWeights,
+    /// Diverged organisms needed to split off a new species
+    pub min_founders: usize,
+    /// No species splits once this many are alive
+    pub max_species: usize,
+    /// How the new species' genes differ from its parent's. The name is
+    /// always derived from the parent's.
+    pub divergence: DivergenceConfig,
+}
+
+impl Default for SpeciationConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.25,
+            weights: DistanceWeights::default(),
+            min_founders: 2,
+            max_species: 8,
+            divergence: DivergenceConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PopulationConfig {
+    /// Organisms bred for each species every generation
+    pub size: usize,
+    pub selection: Selection,
+    /// Fittest organisms of each species copied unchanged into the next
+    /// generation
+    pub elitism: usize,
+    pub crossover: CrossoverPolicy,
+    pub mutation: MutationRates,
+    /// Species never split if unset
+    pub speciation: Option<SpeciationConfig>,
+}
+
+impl Default for PopulationConfig {
+    fn default() -> Self {
+        Self {
+            size: 32,
+            selection: Selection::default(),
+            elitism: 1,
+            crossover: CrossoverPolicy::default(),
+            mutation: MutationRates::default(),
+            speciation: None,
+        }
+    }
+}
+
+/// The organisms of one species in the current generation
+#[derive(Clone, Debug)]
+pub struct Subpopulation {
+    species: SpeciesId,
+    founder: Organism,
+    organisms: Vec<Organism>,
+}
+
+impl Subpopulation {
+    pub fn species(&self) -> SpeciesId {
+        self.species
+    }
+    /// The organism distances are measured from when deciding to split
+    pub fn founder(&self) -> &Organism {
+        &self.founder
+    }
+    pub fn organisms(&self) -> &[Organism] {
+        &self.organisms
+    }
+}
+
+/// Fitness of one species' organisms in a scored generation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpeciesFitness {
+    pub species: SpeciesId,
+    pub best: f32,
+    pub mean: f32,
+}
+
+/// What happened during one [`Population::step`]
+#[derive(Clone, Debug)]
+pub struct GenerationReport {
+    /// The generation that was scored and bred from
+    pub generation: u64,
+    pub fitness: Vec<SpeciesFitness>,
+    /// Species split off from the newly bred generation
+    pub new_species: Vec<SpeciesId>,
+}
+
+/// Evolves organisms over generations: score them with a fitness function,
+/// select parents, then breed and mutate the next generation.
+///
+/// Everything is drawn from substreams of one master seed, so the same
+/// species, config and fitness function always evolve the same way. No
+/// [`App`](bevy::app::App) is needed to run it.
+#[derive(Clone, Debug, Resource)]
+pub struct Population {
+    config: PopulationConfig,
+    seed: GenomeSeed,
+    generation: u64,
+    phylogeny: Phylogeny,
+    subpopulations: Vec<Subpopulation>,
+}
+
+impl Population {
+    /// Found a population of `config.size` organisms generated from `species`.
+    ///
+    /// The species is the root of the population's [`Phylogeny`], so it must
+    /// not name a parent.
+    pub fn new(
+        species: Species,
+        config: PopulationConfig,
+        seed: GenomeSeed,
+    ) -> Result<Self, PopulationError> {
+        if config.size == 0 {
+            return Err(PopulationError::EmptyPopulation);
+        }
+
+        let founders = seed.derive("founders");
+        let organisms = (0..config.size as u64)
+            .map(|i| species.generate(founders.derive_index(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut phylogeny = Phylogeny::new();
+        let species = phylogeny.record(species, 0)?;
+
+        Ok(Self {
+            config,
+            seed,
+            generation: 0,
+            phylogeny,
+            subpopulations: vec![Subpopulation {
+                species,
+                founder: organisms[0].clone(),
+                organisms,
+            }],
+        })
+    }
+
+    pub fn with_default_config(
+        species: Species,
+        seed: GenomeSeed,
+    ) -> Result<Self, PopulationError> {
+        Self::new(species, PopulationConfig::default(), seed)
+    }
+
+    pub fn config(&self) -> &PopulationConfig {
+        &self.config
+    }
+    /// Number of generations bred so far
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Every species the population has held, including the founding one
+    pub fn phylogeny(&self) -> &Phylogeny {
+        &self.phylogeny
+    }
+    pub fn subpopulations(&self) -> &[Subpopulation] {
+        &self.subpopulations
+    }
+    pub fn species(&self, id: SpeciesId) -> &Species {
+        &self.phylogeny.get(id).species
+    }
+
+    /// Every organism in the current generation with its species
+    pub fn organisms(&self) -> impl Iterator<Item = (&Species, &Organism)> {
+        self.subpopulations.iter().flat_map(|sub| {
+            let species = self.species(sub.species);
+            sub.organisms
+                .iter()
+                .map(move |organism| (species, organism))
+        })
+    }
+
+    /// Score the current generation, breed the next one in its place, then
+    /// split off species if [`PopulationConfig::speciation`] is set
+    pub fn step(
+        &mut self,
+        mut fitness: impl FnMut(&Species, &Organism) -> f32,
+    ) -> Result<GenerationReport, PopulationError> {
+        let seed = self.seed.derive("generation").derive_index(self.generation);
+        let mut report = GenerationReport {
+            generation: self.generation,
+            fitness: Vec::new(),
+            new_species: Vec::new(),
+        };
+
+        for (i, sub) in self.subpopulations.iter_mut().enumerate() {
+            let species = &self.phylogeny.get(sub.species).species;
+            let scores: Vec<f32> = sub
+                .organisms
+                .iter()
+                .map(|organism| fitness(species, organism))
+                .collect();
+            report.fitness.push(SpeciesFitness {
+                species: sub.species,
+                best: scores.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+                mean: scores.iter().sum::<f32>() / scores.len() as f32,
+            });
+            sub.organisms = breed(
+                species,
+                &sub.organisms,
+                &scores,
+                &self.config,
+                seed.derive_index(i as u64),
+            );
+        }
+
+        self.generation += 1;
+        if let Some(config) = self.config.speciation.clone() {
+            report.new_species = self.speciate(&config, seed.derive("speciation"))?;
+        }
+        Ok(report)
+    }
+
+    /// Run `generations` steps with the same fitness function
+    pub fn run(
+        &mut self,
+        generations: usize,
+        mut fitness: impl FnMut(&Species, &Organism) -> f32,
+    ) -> Result<Vec<GenerationReport>, PopulationError> {
+        (0..generations).map(|_| self.step(&mut fitness)).collect()
+    }
+
+    /// Move organisms that drifted past the threshold from their species'
+    /// founder into a daughter species
+    fn speciate(
+        &mut self,
+        config: &SpeciationConfig,
+        seed: GenomeSeed,
+    ) -> Result<Vec<SpeciesId>, PopulationError> {
+        let divergence = DivergenceConfig {
+            name: None,
+            ..config.divergence.clone()
+        };
+        let mut new_species = Vec::new();
+
+        for i in 0..self.subpopulations.len() {
+            if self.subpopulations.len() >= config.max_species {
+                break;
+            }
+            let sub = &mut self.subpopulations[i];
+            let diverged: Vec<bool> = sub
+                .organisms
+                .iter()
+                .map(|organism| organism.distance(&sub.founder, &config.weights) > config.threshold)
+                .collect();
+            let count = diverged.iter().filter(|diverged| **diverged).count();
+            // A species that drifted as a whole stays one species
+            if count < config.min_founders.max(1) || count == sub.organisms.len() {
+                continue;
+            }
+
+            let daughter = self
+                .phylogeny
+                .get(sub.species)
+                .species
+                .diverge(&divergence, seed.derive_index(i as u64))?;
+            let species = self.phylogeny.record(daughter, self.generation)?;
+
+            let (moved, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut sub.organisms)
+                .into_iter()
+                .zip(diverged)
+                .partition(|(_, diverged)| *diverged);
+            sub.organisms = kept.into_iter().map(|(organism, _)| organism).collect();
+            // Founders the daughter's genes could not have produced are
+            // replaced by ones generated from it
+            let daughter = &self.phylogeny.get(species).species;
+            let organisms = moved
+                .into_iter()
+                .map(|(organism, _)| {
+                    let founder_seed = organism.genome_seed().derive("founder");
+                    Organism::validate(organism.into(), daughter)
+                        .or_else(|_| daughter.generate(founder_seed))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            self.subpopulations.push(Subpopulation {
+                species,
+                founder: organisms[0].clone(),
+                organisms,
+            });
+            new_species.push(species);
+        }
+        Ok(new_species)
+    }
+}
+
+/// The next generation of one species: its elite, then mutated children of
+/// selected parents. A child that fails validation is replaced by a copy of
+/// its first parent, so one bad draw never stops a generation.
+fn breed(
+    species: &Species,
+    organisms: &[Organism],
+    scores: &[f32],
+    config: &PopulationConfig,
+    seed: GenomeSeed,
+) -> Vec<Organism> {
+    let mut rng: GenomeRng = seed.into();
+
+    let mut ranked: Vec<usize> = (0..organisms.len()).collect();
+    ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+    let mut next: Vec<Organism> = ranked
+        .iter()
+        .take(config.elitism.min(config.size))
+        .map(|&i| organisms[i].clone())
+        .collect();
+
+    while next.len() < config.size {
+        let a = select(config.selection, scores, &mut rng);
+        let b = select(config.selection, scores, &mut rng);
+        let index = rng.random();
+        let child = species
+            .crossover(&organisms[a], &organisms[b], config.crossover, index)
+            .and_then(|child| species.mutate(&child, &config.mutation, index))
+            .unwrap_or_else(|_| organisms[a].clone());
+        next.push(child);
+    }
+    next
+}
+
+fn select(selection: Selection, scores: &[f32], rng: &mut GenomeRng) -> usize {
+    match selection {
+        Selection::Tournament { size } => (0..size.max(1))
+            .map(|_| rng.random_range(0..scores.len()))
+            .max_by(|&a, &b| scores[a].total_cmp(&scores[b]))
+            .unwrap(),
+        Selection::Roulette => {
+            let worst = scores.iter().copied().fold(f32::INFINITY, f32::min);
+            let total: f32 = scores.iter().map(|score| score - worst).sum();
+            // All equal, or scores that are not finite
+            if total.is_nan() || total <= 0.0 || total.is_infinite() {
+                return rng.random_range(0..scores.len());
+            }
+
+            let mut pick = rng.random_range(0.0..total);
+            for (i, score) in scores.iter().enumerate() {
+                pick -= score - worst;
+                if pick < 0.0 {
+                    return i;
+                }
+            }
+            scores.len() - 1
+        }
+    }
+}
@@ -1,15 +1,70 @@
-use rand::{SeedableRng, rngs::StdRng};
+use std::fmt;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The random number generator behind all organism generation.
+///
+/// ChaCha8 is specified independently of the platform and of `rand`'s
+/// `StdRng`, whose algorithm may change between releases. A given
+/// [`GenomeSeed`] keeps producing the same organism from the same species
+/// for as long as this alias and the `rand`/`rand_chacha` minor versions
+/// stay put; changing any of them is a breaking change to saved seeds.
+pub type GenomeRng = ChaCha8Rng;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct GenomeSeed(pub u64);
 
-impl From<GenomeSeed> for StdRng {
+impl GenomeSeed {
+    /// Derive an independent seed for a named part of the organism.
+    ///
+    /// Derivation is a pure function of the seed and label, so a part's
+    /// stream never depends on how many values other parts have drawn.
+    pub fn derive(self, label: &str) -> Self {
+        // FNV-1a over the label keeps the result stable across platforms
+        let hash = label.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        Self(split_mix(self.0 ^ split_mix(hash)))
+    }
+
+    /// Derive an independent seed for the `index`-th of a repeated part
+    pub fn derive_index(self, index: u64) -> Self {
+        Self(split_mix(
+            self.0 ^ split_mix(index.wrapping_add(0x9e37_79b9_7f4a_7c15)),
+        ))
+    }
+
+    /// Seed of the `index`-th offspring of this seed and `other`.
+    ///
+    /// The order of the parents matters, so swapping them gives a different
+    /// brood.
+    pub fn offspring(self, other: GenomeSeed, index: u64) -> Self {
+        self.derive("offspring")
+            .derive_index(other.0)
+            .derive_index(index)
+    }
+}
+
+/// SplitMix64 finalizer, used to decorrelate derived seeds
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl From<GenomeSeed> for GenomeRng {
     fn from(value: GenomeSeed) -> Self {
-        StdRng::seed_from_u64(value.0)
+        GenomeRng::seed_from_u64(value.0)
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f32", into = "f32"))]
 pub struct Normalized(f32);
 
 impl Normalized {
@@ -22,11 +77,14 @@ impl Normalized {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f32", into = "f32"))]
 pub struct Length(f32);
 
 impl Length {
+    /// `None` unless `value` is positive and finite
     pub fn new(value: f32) -> Option<Self> {
-        (value > 0.0).then_some(Self(value))
+        (value > 0.0 && value.is_finite()).then_some(Self(value))
     }
     pub fn value(self) -> f32 {
         self.0
@@ -34,6 +92,8 @@ impl Length {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Radians(f32);
 
 impl Radians {
@@ -46,6 +106,8 @@ impl Radians {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Count(u8);
 
 impl Count {
@@ -57,11 +119,63 @@ impl Count {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ValueRange<T> {
     pub min: T,
     pub max: T,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct VertebraIndex(pub u8);
+
+/// A raw value rejected by a primitive's constructor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InvalidValue {
+    pub type_name: &'static str,
+    pub value: f32,
+}
+
+impl fmt::Display for InvalidValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a valid {}", self.value, self.type_name)
+    }
+}
+
+impl std::error::Error for InvalidValue {}
+
+impl TryFrom<f32> for Normalized {
+    type Error = InvalidValue;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(InvalidValue {
+            type_name: "Normalized",
+            value,
+        })
+    }
+}
+
+impl From<Normalized> for f32 {
+    fn from(value: Normalized) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<f32> for Length {
+    type Error = InvalidValue;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(InvalidValue {
+            type_name: "Length",
+            value,
+        })
+    }
+}
+
+impl From<Length> for f32 {
+    fn from(value: Length) -> Self {
+        value.0
+    }
+}
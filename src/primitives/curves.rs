@@ -1,31 +1,249 @@
 use crate::primitives::*;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurvePoint {
     pub t: Normalized,
     pub value: f32,
 }
 
-#[derive(Clone, Debug)]
+/// How a [`Curve`] fills in values between its points
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Hold each point's value until the next point
+    Step,
+    /// Straight lines between points
+    #[default]
+    Linear,
+    /// Smooth cubic through every point; may overshoot between them
+    CatmullRom,
+    /// Smooth cubic that never overshoots neighbouring values
+    MonotoneCubic,
+}
+
+/// A function over the unit interval defined by points with sorted, unique `t`.
+///
+/// Outside its first and last point the curve holds the end values. An empty
+/// curve evaluates to zero everywhere.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawCurve"))]
 pub struct Curve {
-    pub points: Vec<CurvePoint>,
+    points: Vec<CurvePoint>,
+    interpolation: Interpolation,
+}
+
+impl Curve {
+    /// A linear curve through `points`, or `None` unless their `t` values
+    /// strictly increase
+    pub fn new(points: Vec<CurvePoint>) -> Option<Self> {
+        points
+            .windows(2)
+            .all(|pair| pair[0].t < pair[1].t)
+            .then_some(Self {
+                points,
+                interpolation: Interpolation::default(),
+            })
+    }
+
+    /// A curve with the same value everywhere
+    pub fn constant(value: f32) -> Self {
+        Self {
+            points: vec![CurvePoint {
+                t: Normalized::new(0.0).unwrap(),
+                value,
+            }],
+            interpolation: Interpolation::default(),
+        }
+    }
+
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn points(&self) -> &[CurvePoint] {
+        &self.points
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Value of the curve at `t`, clamped to the end values
+    pub fn evaluate(&self, t: f32) -> f32 {
+        let points = &self.points;
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return 0.0;
+        };
+        if t <= first.t.value() {
+            return first.value;
+        }
+        if t >= last.t.value() {
+            return last.value;
+        }
+
+        // First point past `t`; the clamping above keeps it in 1..len
+        let end = points.partition_point(|point| point.t.value() <= t);
+        let start = end - 1;
+        let (p0, p1) = (&points[start], &points[end]);
+        let span = p1.t.value() - p0.t.value();
+        let u = (t - p0.t.value()) / span;
+
+        match self.interpolation {
+            Interpolation::Step => p0.value,
+            Interpolation::Linear => p0.value + (p1.value - p0.value) * u,
+            Interpolation::CatmullRom => hermite(
+                p0.value,
+                p1.value,
+                self.catmull_rom_tangent(start) * span,
+                self.catmull_rom_tangent(end) * span,
+                u,
+            ),
+            Interpolation::MonotoneCubic => hermite(
+                p0.value,
+                p1.value,
+                self.monotone_tangent(start) * span,
+                self.monotone_tangent(end) * span,
+                u,
+            ),
+        }
+    }
+
+    /// The same curve described by `count` points evenly spaced over the
+    /// unit interval
+    pub fn resample(&self, count: usize) -> Curve {
+        let points = (0..count)
+            .map(|i| {
+                let t = if count > 1 {
+                    i as f32 / (count - 1) as f32
+                } else {
+                    0.0
+                };
+                CurvePoint {
+                    t: Normalized::new(t).unwrap(),
+                    value: self.evaluate(t),
+                }
+            })
+            .collect();
+
+        Self {
+            points,
+            interpolation: self.interpolation,
+        }
+    }
+
+    /// Slope between point `index` and the next
+    fn secant(&self, index: usize) -> f32 {
+        let (p0, p1) = (&self.points[index], &self.points[index + 1]);
+        (p1.value - p0.value) / (p1.t.value() - p0.t.value())
+    }
+
+    /// Central difference across the neighbours, one-sided at the ends
+    fn catmull_rom_tangent(&self, index: usize) -> f32 {
+        let last = self.points.len() - 1;
+        let before = index.saturating_sub(1);
+        let after = (index + 1).min(last);
+        let (p0, p1) = (&self.points[before], &self.points[after]);
+        (p1.value - p0.value) / (p1.t.value() - p0.t.value())
+    }
+
+    /// Fritsch-Butland tangent: zero at local extrema, otherwise a weighted
+    /// harmonic mean of the neighbouring slopes, which keeps each segment
+    /// monotone
+    fn monotone_tangent(&self, index: usize) -> f32 {
+        let last = self.points.len() - 1;
+        if index == 0 {
+            return self.secant(0);
+        }
+        if index == last {
+            return self.secant(last - 1);
+        }
+
+        let (before, after) = (self.secant(index - 1), self.secant(index));
+        if before * after <= 0.0 {
+            return 0.0;
+        }
+        let h0 = self.points[index].t.value() - self.points[index - 1].t.value();
+        let h1 = self.points[index + 1].t.value() - self.points[index].t.value();
+        3.0 * (h0 + h1) / ((2.0 * h1 + h0) / before + (h1 + 2.0 * h0) / after)
+    }
+}
+
+/// Cubic Hermite between `p0` and `p1` with tangents already scaled to the span
+fn hermite(p0: f32, p1: f32, m0: f32, m1: f32, u: f32) -> f32 {
+    let u2 = u * u;
+    let u3 = u2 * u;
+    (2.0 * u3 - 3.0 * u2 + 1.0) * p0
+        + (u3 - 2.0 * u2 + u) * m0
+        + (-2.0 * u3 + 3.0 * u2) * p1
+        + (u3 - u2) * m1
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawCurve {
+    points: Vec<CurvePoint>,
+    #[serde(default)]
+    interpolation: Interpolation,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawCurve> for Curve {
+    type Error = &'static str;
+
+    fn try_from(raw: RawCurve) -> Result<Self, Self::Error> {
+        Curve::new(raw.points)
+            .map(|curve| curve.with_interpolation(raw.interpolation))
+            .ok_or("curve point t values must be sorted and unique")
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f32", into = "f32"))]
 pub struct AspectRatio(f32);
 
 impl AspectRatio {
+    /// `None` unless `value` is positive and finite
     pub fn new(value: f32) -> Option<Self> {
-        (value > 0.0).then_some(Self(value))
+        (value > 0.0 && value.is_finite()).then_some(Self(value))
     }
     pub fn value(self) -> f32 {
         self.0
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CrossSectionProfile {
     Circular,
     Elliptical(AspectRatio),
     Radial(Curve),
 }
+
+impl TryFrom<f32> for AspectRatio {
+    type Error = InvalidValue;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(InvalidValue {
+            type_name: "AspectRatio",
+            value,
+        })
+    }
+}
+
+impl From<AspectRatio> for f32 {
+    fn from(value: AspectRatio) -> Self {
+        value.0
+    }
+}
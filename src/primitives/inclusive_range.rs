@@ -1,6 +1,7 @@
 use crate::primitives::{Count, VertebraIndex};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InclusiveRange<T> {
     pub start: T,
     pub end: T,
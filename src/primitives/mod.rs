@@ -2,10 +2,18 @@ mod core;
 mod curves;
 mod inclusive_range;
 mod non_empty;
+mod sample;
 mod spatial;
+mod weighted;
 
-pub use core::{Count, GenomeSeed, Length, Normalized, Radians, ValueRange, VertebraIndex};
-pub use curves::{AspectRatio, CrossSectionProfile, Curve, CurvePoint};
+pub use core::{
+    Count, GenomeRng, GenomeSeed, InvalidValue, Length, Normalized, Radians, ValueRange,
+    VertebraIndex,
+};
+pub use curves::{AspectRatio, CrossSectionProfile, Curve, CurvePoint, Interpolation};
 pub use inclusive_range::InclusiveRange;
 pub use non_empty::NonEmpty;
+pub use sample::Sample;
+pub(crate) use sample::lerp;
 pub use spatial::LocalPosition;
+pub use weighted::{Weighted, WeightedChoice};
@@ -1,4 +1,6 @@
-#[derive(Clone, Debug)]
+use rand::Rng;
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct NonEmpty<T> {
     first: T,
     rest: Vec<T>,
@@ -29,11 +31,63 @@ impl<T> NonEmpty<T> {
         &self.first
     }
 
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match index {
+            0 => Some(&self.first),
+            i => self.rest.get(i - 1),
+        }
+    }
+
+    /// Pick one entry uniformly at random
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> &T {
+        self.get(rng.random_range(0..self.len()))
+            .expect("index drawn from within list length")
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         std::iter::once(&self.first).chain(self.rest.iter())
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        std::iter::once(&mut self.first).chain(self.rest.iter_mut())
+    }
+
+    /// Remove the last entry, unless it is the only one
+    pub fn pop(&mut self) -> Option<T> {
+        self.rest.pop()
+    }
+
     pub fn len(&self) -> usize {
         1 + self.rest.len()
     }
+
+    /// Always false; provided for parity with `len`
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = std::iter::Chain<std::iter::Once<T>, std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self.first).chain(self.rest)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for NonEmpty<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for NonEmpty<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = Vec::<T>::deserialize(deserializer)?;
+        Self::from_vec(items)
+            .ok_or_else(|| serde::de::Error::invalid_length(0, &"at least one element"))
+    }
 }
@@ -0,0 +1,107 @@
+use bevy::prelude::{LinearRgba, Mix};
+use rand::Rng;
+
+use crate::primitives::*;
+
+/// Gene containers that can draw a concrete value from a random source
+pub trait Sample {
+    type Output;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Output;
+}
+
+/// Interpolate between two bounds at a uniformly drawn position
+pub(crate) fn lerp<R: Rng + ?Sized>(min: f32, max: f32, rng: &mut R) -> f32 {
+    min + (max - min) * rng.random::<f32>()
+}
+
+impl Sample for ValueRange<f32> {
+    type Output = f32;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        lerp(self.min, self.max, rng)
+    }
+}
+
+impl Sample for ValueRange<Normalized> {
+    type Output = Normalized;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Normalized {
+        Normalized::new(lerp(self.min.value(), self.max.value(), rng).clamp(0.0, 1.0))
+            .expect("clamped into unit range")
+    }
+}
+
+impl Sample for ValueRange<Length> {
+    type Output = Length;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Length {
+        Length::new(lerp(self.min.value(), self.max.value(), rng))
+            .expect("interpolating between positive lengths stays positive")
+    }
+}
+
+impl Sample for ValueRange<Radians> {
+    type Output = Radians;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Radians {
+        Radians::new(lerp(self.min.value(), self.max.value(), rng))
+    }
+}
+
+impl Sample for ValueRange<LinearRgba> {
+    type Output = LinearRgba;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> LinearRgba {
+        self.min.mix(&self.max, rng.random())
+    }
+}
+
+/// Samples each point's value independently, pairing points of the two bounds
+/// by index. Point positions share one mix so they stay in order. Points
+/// beyond the shorter bound are ignored; the result interpolates like `min`.
+impl Sample for ValueRange<Curve> {
+    type Output = Curve;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Curve {
+        let pairs: Vec<_> = self.min.points().iter().zip(self.max.points()).collect();
+        let values: Vec<f32> = pairs
+            .iter()
+            .map(|(min, max)| lerp(min.value, max.value, rng))
+            .collect();
+        let mix = rng.random::<f32>();
+
+        let points = pairs
+            .iter()
+            .zip(values)
+            .map(|((min, max), value)| CurvePoint {
+                t: Normalized::new(min.t.value() + (max.t.value() - min.t.value()) * mix)
+                    .expect("interpolating between unit values stays in unit range"),
+                value,
+            })
+            .collect();
+
+        // Rounding can collapse nearly coincident points; fall back to a bound
+        Curve::new(points)
+            .unwrap_or_else(|| self.min.clone())
+            .with_interpolation(self.min.interpolation())
+    }
+}
+
+impl Sample for InclusiveRange<Count> {
+    type Output = Count;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Count {
+        let low = self.start.min(self.end);
+        let high = self.start.max(self.end);
+        Count::new(rng.random_range(low.value()..=high.value()))
+    }
+}
+
+impl<T: Clone> Sample for NonEmpty<T> {
+    type Output = T;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.choose(rng).clone()
+    }
+}
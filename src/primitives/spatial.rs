@@ -1,6 +1,8 @@
 use bevy::prelude::Vec3;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct LocalPosition(pub Vec3);
 
 impl LocalPosition {
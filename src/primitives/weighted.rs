@@ -0,0 +1,122 @@
+use rand::Rng;
+
+use crate::primitives::{NonEmpty, Sample};
+
+/// A value paired with its relative selection weight
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Weighted<T> {
+    pub value: T,
+    pub weight: f32,
+}
+
+impl<T> Weighted<T> {
+    pub fn new(value: T, weight: f32) -> Self {
+        Self { value, weight }
+    }
+}
+
+/// A non-empty list of choices picked in proportion to their weights
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct WeightedChoice<T> {
+    entries: NonEmpty<Weighted<T>>,
+}
+
+impl<T> WeightedChoice<T> {
+    pub fn new(value: T, weight: f32) -> Self {
+        Self {
+            entries: NonEmpty::new(Weighted::new(value, weight)),
+        }
+    }
+
+    pub fn from_vec(entries: Vec<Weighted<T>>) -> Option<Self> {
+        NonEmpty::from_vec(entries).map(|entries| Self { entries })
+    }
+
+    /// Give every entry the same weight
+    pub fn uniform(values: NonEmpty<T>) -> Self {
+        let mut values = values.into_iter();
+        let first = values.next().expect("non-empty list has a first entry");
+        let mut choice = Self::new(first, 1.0);
+        for value in values {
+            choice.push(value, 1.0);
+        }
+        choice
+    }
+
+    pub fn push(&mut self, value: T, weight: f32) {
+        self.entries.push(Weighted::new(value, weight));
+    }
+
+    pub fn entries(&self) -> &NonEmpty<Weighted<T>> {
+        &self.entries
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Weighted<T>> {
+        self.entries.iter()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter().map(|entry| &entry.value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entries.iter_mut().map(|entry| &mut entry.value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Always false; provided for parity with `len`
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.entries.iter().map(|entry| entry.weight).sum()
+    }
+
+    /// Weights must be finite and non-negative, and sum above zero
+    pub fn has_valid_weights(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| entry.weight.is_finite() && entry.weight >= 0.0)
+            && self.total_weight() > 0.0
+    }
+
+    /// Pick one entry in proportion to its weight.
+    /// Falls back to a uniform pick if the weights are invalid.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> &T {
+        if !self.has_valid_weights() {
+            return &self.entries.choose(rng).value;
+        }
+
+        let mut remaining = rng.random::<f32>() * self.total_weight();
+        let mut chosen = self.entries.first();
+        for entry in self.entries.iter().filter(|entry| entry.weight > 0.0) {
+            chosen = entry;
+            if remaining < entry.weight {
+                break;
+            }
+            remaining -= entry.weight;
+        }
+        &chosen.value
+    }
+}
+
+impl<T> From<NonEmpty<T>> for WeightedChoice<T> {
+    fn from(values: NonEmpty<T>) -> Self {
+        Self::uniform(values)
+    }
+}
+
+impl<T: Clone> Sample for WeightedChoice<T> {
+    type Output = T;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.choose(rng).clone()
+    }
+}
@@ -1,34 +1,45 @@
-use crate::appendage::AppendageClass;
+use crate::{anatomical_features::SensoryType, appendage::AppendageClass};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Side {
     Left,
     Right,
 }
 
-impl Side {
-    pub fn mirror_x(&self) -> f32 {
-        match self {
-            Side::Left => 1.0,
-            Side::Right => -1.0,
-        }
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoneClass {
     Root,
     Head,
     Mandible,
     Spine,
     Limb(AppendageClass),
-    Digit,
+    /// One digit of the terminus of a limb of the given class
+    Digit(AppendageClass),
+    /// One segment of a horn, from its base outward
+    Horn,
+    /// One spike of a row of spines
+    Spike,
+    /// One ray supporting a fin
+    FinRay,
+    /// Attach point for a sensory organ, such as a movable eye or ear
+    Sensory(SensoryType),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoneId {
     pub class: BoneClass,
     pub side: Option<Side>,
+    /// Which fold of a radial body the bone repeats in
+    pub fold: Option<u8>,
+    /// Vertebra an appendage or feature bone grows on
+    pub vertebra: Option<u8>,
+    /// Socket a feature or appendage bone grows from: its index among the
+    /// head's feature sockets, or among the spine's feature or appendage
+    /// attachments
+    pub socket: Option<u8>,
     pub index: u8,
     /// Path through branch points for nested structures (e.g., fingers)
     pub branch_path: Vec<u8>,
@@ -39,6 +50,9 @@ impl BoneId {
         Self {
             class: BoneClass::Root,
             side: None,
+            fold: None,
+            vertebra: None,
+            socket: None,
             index: 0,
             branch_path: Vec::new(),
         }
@@ -48,6 +62,9 @@ impl BoneId {
         Self {
             class: BoneClass::Head,
             side: None,
+            fold: None,
+            vertebra: None,
+            socket: None,
             index: 0,
             branch_path: Vec::new(),
         }
@@ -57,6 +74,9 @@ impl BoneId {
         Self {
             class: BoneClass::Mandible,
             side: None,
+            fold: None,
+            vertebra: None,
+            socket: None,
             index: 0,
             branch_path: Vec::new(),
         }
@@ -66,6 +86,9 @@ impl BoneId {
         Self {
             class: BoneClass::Spine,
             side: None,
+            fold: None,
+            vertebra: None,
+            socket: None,
             index,
             branch_path: Vec::new(),
         }
@@ -75,25 +98,59 @@ impl BoneId {
         Self {
             class: BoneClass::Limb(class),
             side,
+            fold: None,
+            vertebra: None,
+            socket: None,
             index,
             branch_path: Vec::new(),
         }
     }
 
+    pub fn with_fold(mut self, fold: u8) -> Self {
+        self.fold = Some(fold);
+        self
+    }
+
+    pub fn with_vertebra(mut self, vertebra: u8) -> Self {
+        self.vertebra = Some(vertebra);
+        self
+    }
+
+    pub fn with_socket(mut self, socket: u8) -> Self {
+        self.socket = Some(socket);
+        self
+    }
+
     pub fn with_branch(mut self, branch_index: u8) -> Self {
         self.branch_path.push(branch_index);
         self
     }
 
-    pub fn digit(side: Option<Side>, digit_index: u8) -> Self {
+    pub fn digit(class: AppendageClass, side: Option<Side>, digit_index: u8) -> Self {
         Self {
-            class: BoneClass::Digit,
+            class: BoneClass::Digit(class),
             side,
+            fold: None,
+            vertebra: None,
+            socket: None,
             index: digit_index,
             branch_path: Vec::new(),
         }
     }
 
+    /// A bone of an anatomical feature or sensory organ
+    pub fn feature(class: BoneClass, side: Option<Side>, index: u8) -> Self {
+        Self {
+            class,
+            side,
+            fold: None,
+            vertebra: None,
+            socket: None,
+            index,
+            branch_path: Vec::new(),
+        }
+    }
+
     /// Generate a deterministic name for animation targeting
     pub fn name(&self) -> String {
         let mut name = match self.class {
@@ -101,18 +158,20 @@ impl BoneId {
             BoneClass::Head => "head".to_string(),
             BoneClass::Mandible => "mandible".to_string(),
             BoneClass::Spine => format!("spine_{}", self.index),
-            BoneClass::Limb(class) => {
-                let class_name = match class {
-                    AppendageClass::Forelimb => "forelimb",
-                    AppendageClass::Hindlimb => "hindlimb",
-                    AppendageClass::Wing => "wing",
-                    AppendageClass::Tentacle => "tentacle",
-                    AppendageClass::Tail => "tail",
-                    AppendageClass::Antenna => "antenna",
+            BoneClass::Limb(class) => format!("{}_{}", limb_name(class), self.index),
+            BoneClass::Digit(class) => format!("{}_digit_{}", limb_name(class), self.index),
+            BoneClass::Horn => format!("horn_{}", self.index),
+            BoneClass::Spike => format!("spike_{}", self.index),
+            BoneClass::FinRay => format!("fin_ray_{}", self.index),
+            BoneClass::Sensory(kind) => {
+                let organ = match kind {
+                    SensoryType::Ocular => "eye",
+                    SensoryType::Auditory => "ear",
+                    SensoryType::Olfactory => "nose",
+                    SensoryType::Tactile => "whisker",
                 };
-                format!("{}_{}", class_name, self.index)
+                format!("{}_{}", organ, self.index)
             }
-            BoneClass::Digit => format!("digit_{}", self.index),
         };
 
         if let Some(side) = self.side {
@@ -123,6 +182,18 @@ impl BoneId {
             name.push_str(suffix);
         }
 
+        if let Some(vertebra) = self.vertebra {
+            name.push_str(&format!("_v{vertebra}"));
+        }
+
+        if let Some(socket) = self.socket {
+            name.push_str(&format!("_s{socket}"));
+        }
+
+        if let Some(fold) = self.fold {
+            name.push_str(&format!("_f{fold}"));
+        }
+
         for (i, branch) in self.branch_path.iter().enumerate() {
             name.push_str(&format!("_b{}_{}", i, branch));
         }
@@ -130,3 +201,14 @@ impl BoneId {
         name
     }
 }
+
+fn limb_name(class: AppendageClass) -> &'static str {
+    match class {
+        AppendageClass::Forelimb => "forelimb",
+        AppendageClass::Hindlimb => "hindlimb",
+        AppendageClass::Wing => "wing",
+        AppendageClass::Tentacle => "tentacle",
+        AppendageClass::Tail => "tail",
+        AppendageClass::Antenna => "antenna",
+    }
+}
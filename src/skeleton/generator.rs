@@ -0,0 +1,705 @@
+use bevy::prelude::{Dir3, Quat, Resource, Vec3};
+
+use crate::{
+    anatomical_features::{
+        AnatomicalFeature, FinStructure, MandibleStructure, Protrusion, SensoryOrgan, SpineRow,
+    },
+    appendage::{Appendage, AppendageClass, LimbStructure, Terminus},
+    organism::Organism,
+    primitives::{Curve, CurvePoint, Length, LocalPosition, Normalized},
+    sockets_symmetry::{
+        BODY_AXIS, LATERAL_AXIS, Socket, SymmetricSocket, fold_rotation, mirror_lateral,
+    },
+    tissue_muscle::TissueEnvelope,
+};
+
+use super::{
+    BoneClass, BoneId, Side,
+    node::{GeneratedSkeleton, RestTransform, SkeletonNode},
+    traits::{Articulated, BoneSource, Terminable},
+};
+
+/// Configuration for skeleton generation
+#[derive(Clone, Debug, Resource)]
+pub struct SkeletonConfig {
+    /// Primary axis along which bones extend (typically forward/back)
+    pub bone_axis: Vec3,
+    /// Axis branches and digits spread along (typically left/right). Right-side
+    /// spreads mirror across [`LATERAL_AXIS`], as right-side sockets do
+    pub lateral_axis: Vec3,
+    /// Emit bones for horns, spine rows, fins and sensory organs
+    pub feature_bones: bool,
+    /// Bones a horn is split into so it can follow its curvature
+    pub horn_segments: u8,
+    /// Rays spread along the base of a fin
+    pub fin_rays: u8,
+}
+
+/// Local axis feature bones extend along; their rest rotation turns it onto
+/// the socket normal
+const FEATURE_AXIS: Vec3 = Vec3::Y;
+
+impl Default for SkeletonConfig {
+    fn default() -> Self {
+        Self {
+            bone_axis: Vec3::NEG_Z, // bones extend backward
+            lateral_axis: Vec3::X,  // bilateral = left/right
+            feature_bones: true,
+            horn_segments: 4,
+            fin_rays: 5,
+        }
+    }
+}
+
+/// Generates skeleton hierarchies from organisms
+pub struct SkeletonGenerator {
+    config: SkeletonConfig,
+}
+
+impl SkeletonGenerator {
+    pub fn new(config: SkeletonConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn with_default_config() -> Self {
+        Self::new(SkeletonConfig::default())
+    }
+
+    /// Generate a complete skeleton from an organism
+    pub fn generate(&self, organism: &Organism) -> GeneratedSkeleton {
+        // Create root node (synthetic, zero-length)
+        let root_length = Length::new(0.001).unwrap(); // near-zero
+        let mut root = SkeletonNode::new(BoneId::root(), root_length);
+
+        // Add head
+        let head_node = self.generate_head(organism);
+        root.add_child(head_node);
+
+        // Add spine and collect attachment points
+        let spine_nodes = self.generate_spine(organism);
+        for spine_node in spine_nodes {
+            root.add_child(spine_node);
+        }
+
+        GeneratedSkeleton::new(root)
+    }
+
+    fn generate_head(&self, organism: &Organism) -> SkeletonNode {
+        let cranium = organism.head();
+        let head_length = cranium.length();
+
+        let mut head_node = SkeletonNode::new(BoneId::head(), head_length)
+            .with_rest(RestTransform::from_offset_along_parent(
+                0.0, // Head at origin, spine extends backward
+                self.config.bone_axis,
+            ))
+            .with_axis(-self.config.bone_axis)
+            .with_tissue(cranium.bone().tissue.clone());
+
+        // Add mandible if present
+        if let Some(mandible_socket) = &cranium.mandible_socket
+            && let Some(mandible) = &mandible_socket.attachment
+        {
+            let mandible_node = self.generate_mandible(
+                mandible_socket.position,
+                mandible_socket.normal,
+                mandible,
+                None,
+            );
+            head_node.add_child(mandible_node);
+        }
+
+        if self.config.feature_bones {
+            for (i, socket) in cranium.sensory_sockets.iter().enumerate() {
+                for node in self.generate_from_socket(socket, |organ, socket, side| {
+                    vec![self.generate_sensory(organ, socket, side, i as u8)]
+                }) {
+                    head_node.add_child(node);
+                }
+            }
+            for (i, socket) in cranium.feature_sockets.iter().enumerate() {
+                for mut node in self.generate_from_socket(socket, |feature, socket, side| {
+                    self.generate_feature(feature, socket, side)
+                }) {
+                    tag_socket(&mut node, i as u8);
+                    head_node.add_child(node);
+                }
+            }
+        }
+
+        head_node
+    }
+
+    /// Mandible segments as a chain hanging from a mandible socket, each one
+    /// jointed to the next by its distal joint
+    fn generate_mandible(
+        &self,
+        position: LocalPosition,
+        normal: Dir3,
+        mandible: &MandibleStructure,
+        side: Option<Side>,
+    ) -> SkeletonNode {
+        let axis = normal.as_vec3();
+        let mut nodes: Vec<SkeletonNode> = mandible
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                let rest = match i {
+                    0 => RestTransform::from_translation(position.as_vec3()),
+                    _ => RestTransform::from_offset_along_parent(
+                        mandible.segments[i - 1].length().value(),
+                        axis,
+                    ),
+                };
+                let mut node = SkeletonNode::new(
+                    BoneId {
+                        side,
+                        index: i as u8,
+                        ..BoneId::mandible()
+                    },
+                    segment.length(),
+                )
+                .with_rest(rest)
+                .with_axis(axis)
+                .with_tissue(segment.bone().tissue.clone());
+                if let Some(art) = segment.articulation() {
+                    node = node.with_articulation(*art);
+                }
+                node
+            })
+            .collect();
+
+        // Nest from the tip back so each segment is the parent of the next
+        let Some(mut chain) = nodes.pop() else {
+            let id = BoneId {
+                side,
+                ..BoneId::mandible()
+            };
+            return SkeletonNode::new(id, Length::new(0.1).unwrap())
+                .with_rest(RestTransform::from_translation(position.as_vec3()))
+                .with_axis(axis);
+        };
+        while let Some(mut parent) = nodes.pop() {
+            parent.add_child(chain);
+            chain = parent;
+        }
+        chain
+    }
+
+    fn generate_spine(&self, organism: &Organism) -> Vec<SkeletonNode> {
+        let spine = &organism.torso().spine;
+        let mut spine_nodes = Vec::new();
+        let mut cumulative_offset = 0.0f32;
+
+        for (i, vertebra) in spine.vertebrae.iter().enumerate() {
+            let vert_length = vertebra.length();
+
+            let mut vert_node = SkeletonNode::new(BoneId::spine(i as u8), vert_length)
+                .with_rest(RestTransform::from_offset_along_parent(
+                    cumulative_offset,
+                    self.config.bone_axis,
+                ))
+                .with_axis(self.config.bone_axis)
+                .with_tissue(vertebra.bone().tissue.clone());
+
+            // Find appendages attached to this vertebra
+            for (socket_index, attachment) in spine.appendages.iter().enumerate() {
+                if attachment.vertebra_index.0 as usize == i {
+                    let appendage_nodes = self.generate_appendage_from_socket(&attachment.socket);
+                    for mut app_node in appendage_nodes {
+                        tag_vertebra(&mut app_node, i as u8);
+                        tag_socket(&mut app_node, socket_index as u8);
+                        vert_node.add_child(app_node);
+                    }
+                }
+            }
+
+            if self.config.feature_bones {
+                for (socket_index, attachment) in spine.features.iter().enumerate() {
+                    if attachment.vertebra_index.0 as usize == i {
+                        for mut node in self
+                            .generate_from_socket(&attachment.socket, |feature, socket, side| {
+                                self.generate_feature(feature, socket, side)
+                            })
+                        {
+                            tag_vertebra(&mut node, i as u8);
+                            tag_socket(&mut node, socket_index as u8);
+                            vert_node.add_child(node);
+                        }
+                    }
+                }
+            }
+
+            cumulative_offset += vert_length.value();
+            spine_nodes.push(vert_node);
+        }
+
+        spine_nodes
+    }
+
+    fn generate_appendage_from_socket(
+        &self,
+        socket: &SymmetricSocket<Appendage>,
+    ) -> Vec<SkeletonNode> {
+        match socket {
+            SymmetricSocket::Medial(s) => {
+                if let Some(appendage) = &s.attachment {
+                    vec![self.generate_appendage(appendage, self.socket_rest(s, appendage), None)]
+                } else {
+                    vec![]
+                }
+            }
+            SymmetricSocket::Lateral(pair) => {
+                let mut nodes = Vec::new();
+
+                if let Some(left_app) = &pair.left.attachment {
+                    let rest = self.socket_rest(&pair.left, left_app);
+                    nodes.push(self.generate_appendage(left_app, rest, Some(Side::Left)));
+                }
+                if let Some(right_app) = &pair.right.attachment {
+                    let rest = self.socket_rest(&pair.right, right_app);
+                    nodes.push(self.generate_appendage(right_app, rest, Some(Side::Right)));
+                }
+
+                nodes
+            }
+            SymmetricSocket::Radial(folds) => (0..folds.len())
+                .filter_map(|fold| self.generate_fold(folds, fold))
+                .collect(),
+        }
+    }
+
+    /// A radial appendage at its fold's socket. Rest rotations are the first
+    /// fold's turned about [`BODY_AXIS`], the axis the sockets were spread
+    /// around.
+    fn generate_fold(&self, folds: &[Socket<Appendage>], fold: usize) -> Option<SkeletonNode> {
+        let socket = &folds[fold];
+        let appendage = socket.attachment.as_ref()?;
+        let rest = RestTransform::from_translation(socket.position.as_vec3()).with_rotation(
+            fold_rotation(BODY_AXIS, fold as u8, folds.len() as u8)
+                * self.aim(appendage.class, folds[0].normal),
+        );
+
+        let mut node = self.generate_appendage(appendage, rest, None);
+        tag_fold(&mut node, fold as u8);
+        Some(node)
+    }
+
+    /// Bones built by `build` for each filled side or fold of a socket. Radial
+    /// folds are built at their own sockets and tagged with their fold.
+    fn generate_from_socket<T>(
+        &self,
+        socket: &SymmetricSocket<T>,
+        build: impl Fn(&T, &Socket<T>, Option<Side>) -> Vec<SkeletonNode>,
+    ) -> Vec<SkeletonNode> {
+        let fill = |socket: &Socket<T>, side| {
+            socket
+                .attachment
+                .as_ref()
+                .map(|attachment| build(attachment, socket, side))
+                .unwrap_or_default()
+        };
+        match socket {
+            SymmetricSocket::Medial(s) => fill(s, None),
+            SymmetricSocket::Lateral(pair) => {
+                let mut nodes = fill(&pair.left, Some(Side::Left));
+                nodes.extend(fill(&pair.right, Some(Side::Right)));
+                nodes
+            }
+            SymmetricSocket::Radial(folds) => folds
+                .iter()
+                .enumerate()
+                .flat_map(|(fold, socket)| {
+                    let mut nodes = fill(socket, None);
+                    for node in &mut nodes {
+                        tag_fold(node, fold as u8);
+                    }
+                    nodes
+                })
+                .collect(),
+        }
+    }
+
+    /// Bones for the feature on one side or fold of a feature socket. Callers
+    /// tag them with their socket, which keeps features of the same kind on
+    /// different sockets apart.
+    fn generate_feature(
+        &self,
+        feature: &AnatomicalFeature,
+        socket: &Socket<AnatomicalFeature>,
+        side: Option<Side>,
+    ) -> Vec<SkeletonNode> {
+        let (position, normal) = (socket.position.as_vec3(), socket.normal);
+        match feature {
+            AnatomicalFeature::Sensory(organ) => {
+                vec![self.generate_sensory(organ, socket, side, 0)]
+            }
+            AnatomicalFeature::Horn(horn) => vec![self.generate_horn(horn, position, normal, side)],
+            AnatomicalFeature::Spines(row) => self.generate_spikes(row, position, normal, side),
+            AnatomicalFeature::Fin(fin) => self.generate_fin_rays(fin, position, normal, side),
+            AnatomicalFeature::Mandible(mandible) => {
+                vec![self.generate_mandible(socket.position, normal, mandible, side)]
+            }
+        }
+    }
+
+    /// Attach point for a sensory organ: one bone, as long as the organ's size
+    fn generate_sensory<T>(
+        &self,
+        organ: &SensoryOrgan,
+        socket: &Socket<T>,
+        side: Option<Side>,
+        index: u8,
+    ) -> SkeletonNode {
+        SkeletonNode::new(
+            BoneId::feature(BoneClass::Sensory(organ.kind), side, index),
+            organ.size,
+        )
+        .with_rest(self.feature_rest(socket.position.as_vec3(), socket.normal))
+        .with_axis(FEATURE_AXIS)
+    }
+
+    /// A horn as a chain of equal segments. `curvature` is read as the bend
+    /// in radians per unit length along the horn, so each joint turns about
+    /// the lateral axis by the curvature at that joint times the segment
+    /// length.
+    fn generate_horn(
+        &self,
+        horn: &Protrusion,
+        position: Vec3,
+        normal: Dir3,
+        side: Option<Side>,
+    ) -> SkeletonNode {
+        let count = self.config.horn_segments.max(1);
+        let segments = count as f32;
+        let segment_length = horn.length.value() / segments;
+        let length = Length::new(segment_length).unwrap_or(horn.length);
+        let bend_axis = self.config.lateral_axis.normalize();
+
+        let mut nodes: Vec<SkeletonNode> = (0..count)
+            .map(|i| {
+                let t = i as f32 / segments;
+                let rest = match i {
+                    0 => self.feature_rest(position, normal),
+                    _ => RestTransform::from_offset_along_parent(segment_length, FEATURE_AXIS)
+                        .with_rotation(Quat::from_axis_angle(
+                            bend_axis,
+                            horn.curvature.evaluate(t) * segment_length,
+                        )),
+                };
+                SkeletonNode::new(BoneId::feature(BoneClass::Horn, side, i), length)
+                    .with_rest(rest)
+                    .with_axis(FEATURE_AXIS)
+                    .with_tissue(horn_segment_tissue(
+                        &horn.tissue,
+                        t,
+                        (i + 1) as f32 / segments,
+                    ))
+            })
+            .collect();
+
+        // Nest from the tip back so each segment is the parent of the next
+        let mut chain = nodes.pop().expect("horns have at least one segment");
+        while let Some(mut parent) = nodes.pop() {
+            parent.add_child(chain);
+            chain = parent;
+        }
+        chain
+    }
+
+    /// One bone per spike, spaced along the body from the socket
+    fn generate_spikes(
+        &self,
+        row: &SpineRow,
+        position: Vec3,
+        normal: Dir3,
+        side: Option<Side>,
+    ) -> Vec<SkeletonNode> {
+        let step = self.config.bone_axis.normalize() * row.spacing.value();
+        (0..row.count.value())
+            .map(|i| {
+                SkeletonNode::new(BoneId::feature(BoneClass::Spike, side, i), row.spine_length)
+                    .with_rest(self.feature_rest(position + step * i as f32, normal))
+                    .with_axis(FEATURE_AXIS)
+            })
+            .collect()
+    }
+
+    /// Rays as tall as the fin, spread evenly along its base from the socket
+    fn generate_fin_rays(
+        &self,
+        fin: &FinStructure,
+        position: Vec3,
+        normal: Dir3,
+        side: Option<Side>,
+    ) -> Vec<SkeletonNode> {
+        let rays = self.config.fin_rays;
+        let step = self.config.bone_axis.normalize() * fin.length.value()
+            / rays.saturating_sub(1).max(1) as f32;
+        (0..rays)
+            .map(|i| {
+                SkeletonNode::new(BoneId::feature(BoneClass::FinRay, side, i), fin.height)
+                    .with_rest(self.feature_rest(position + step * i as f32, normal))
+                    .with_axis(FEATURE_AXIS)
+            })
+            .collect()
+    }
+
+    /// Rest pose of a feature's root bone: at `position`, turned so
+    /// [`FEATURE_AXIS`] points along the socket normal
+    fn feature_rest(&self, position: Vec3, normal: Dir3) -> RestTransform {
+        RestTransform::from_translation(position).with_rotation(arc(FEATURE_AXIS, normal.as_vec3()))
+    }
+
+    /// Rest pose of an appendage's root bone: at its socket, relative to the
+    /// vertebra, and turned to extend along the socket's normal
+    fn socket_rest(&self, socket: &Socket<Appendage>, appendage: &Appendage) -> RestTransform {
+        RestTransform::from_translation(socket.position.as_vec3())
+            .with_rotation(self.aim(appendage.class, socket.normal))
+    }
+
+    /// Rotation taking a class's limb axis onto `direction`
+    fn aim(&self, class: AppendageClass, direction: Dir3) -> Quat {
+        arc(
+            self.limb_axis_for_class(class).normalize(),
+            direction.as_vec3(),
+        )
+    }
+
+    /// Reflect an offset the generator spreads bones along onto the right
+    /// side, the same way right-side sockets are
+    fn mirrored(&self, offset: Vec3, side: Option<Side>) -> Vec3 {
+        match side {
+            Some(Side::Right) => mirror_lateral(offset),
+            _ => offset,
+        }
+    }
+
+    fn generate_appendage(
+        &self,
+        appendage: &Appendage,
+        rest: RestTransform,
+        side: Option<Side>,
+    ) -> SkeletonNode {
+        self.generate_limb(
+            &appendage.structure,
+            appendage.class,
+            side,
+            rest,
+            Vec::new(),
+        )
+    }
+
+    fn generate_limb(
+        &self,
+        limb: &LimbStructure,
+        class: AppendageClass,
+        side: Option<Side>,
+        rest: RestTransform,
+        branch_path: Vec<u8>,
+    ) -> SkeletonNode {
+        // Start with first segment
+        let first_seg = limb.segments.first();
+        let first_length = first_seg
+            .map(|s| s.length())
+            .unwrap_or_else(|| Length::new(0.1).unwrap());
+
+        let mut root_id = BoneId::limb(class, side, 0);
+        root_id.branch_path = branch_path.clone();
+
+        let mut root_node = SkeletonNode::new(root_id, first_length)
+            .with_rest(rest)
+            .with_axis(self.limb_axis_for_class(class));
+
+        if let Some(seg) = first_seg {
+            root_node = root_node.with_tissue(seg.bone().tissue.clone());
+            if let Some(art) = seg.articulation() {
+                root_node = root_node.with_articulation(*art);
+            }
+        }
+
+        // Chain remaining segments
+        let mut parent_node = &mut root_node;
+        let mut cumulative_length = first_length.value();
+
+        for (i, segment) in limb.segments.iter().enumerate().skip(1) {
+            let seg_length = segment.length();
+
+            let mut seg_id = BoneId::limb(class, side, i as u8);
+            seg_id.branch_path = branch_path.clone();
+
+            let mut seg_node = SkeletonNode::new(seg_id, seg_length)
+                .with_rest(RestTransform::from_offset_along_parent(
+                    cumulative_length,
+                    self.limb_axis_for_class(class),
+                ))
+                .with_axis(self.limb_axis_for_class(class))
+                .with_tissue(segment.bone().tissue.clone());
+
+            if let Some(art) = segment.articulation() {
+                seg_node = seg_node.with_articulation(*art);
+            }
+
+            // Check for branching at this segment
+            if let Some(branch_point) = &limb.branching
+                && branch_point.parent_segment == i
+            {
+                let branch_nodes = self.generate_branches(branch_point, class, side, &branch_path);
+                for bn in branch_nodes {
+                    seg_node.add_child(bn);
+                }
+            }
+
+            parent_node.add_child(seg_node);
+
+            // Navigate to the child we just added
+            let last_idx = parent_node.children.len() - 1;
+            parent_node = &mut parent_node.children[last_idx];
+            cumulative_length = seg_length.value();
+        }
+
+        // Add terminus bones
+        let terminus_nodes = self.generate_terminus(&limb.terminus, class, side, &branch_path);
+        for tn in terminus_nodes {
+            parent_node.add_child(tn);
+        }
+
+        root_node
+    }
+
+    fn generate_branches(
+        &self,
+        branch_point: &crate::appendage::BranchPoint,
+        class: AppendageClass,
+        side: Option<Side>,
+        parent_path: &[u8],
+    ) -> Vec<SkeletonNode> {
+        let mut nodes = Vec::new();
+
+        for branch_idx in 0..branch_point.branch_count.value() {
+            let mut new_path = parent_path.to_vec();
+            new_path.push(branch_idx);
+
+            // Calculate spread angle for this branch
+            let spread = self.branch_spread_offset(branch_idx, branch_point.branch_count.value());
+
+            let rest = RestTransform::from_translation(
+                self.mirrored(self.config.lateral_axis * spread, side),
+            );
+            let branch_node = self.generate_limb(&branch_point.branch, class, side, rest, new_path);
+            nodes.push(branch_node);
+        }
+
+        nodes
+    }
+
+    fn generate_terminus(
+        &self,
+        terminus: &Terminus,
+        class: AppendageClass,
+        side: Option<Side>,
+        branch_path: &[u8],
+    ) -> Vec<SkeletonNode> {
+        let bone_count = terminus.terminal_bone_count();
+
+        if bone_count == 0 {
+            return vec![];
+        }
+
+        let mut nodes = Vec::new();
+
+        // TODO: Get actual digit length from somewhere (needs type extension)
+        let digit_length = Length::new(0.1).unwrap();
+
+        for i in 0..bone_count {
+            let spread = self.branch_spread_offset(i, bone_count);
+
+            let mut id = BoneId::digit(class, side, i);
+            id.branch_path = branch_path.to_vec();
+            let node =
+                SkeletonNode::new(id, digit_length).with_rest(RestTransform::from_translation(
+                    self.mirrored(self.config.lateral_axis * spread, side),
+                ));
+
+            nodes.push(node);
+        }
+
+        nodes
+    }
+
+    /// Get the primary axis for limb extension based on appendage class
+    fn limb_axis_for_class(&self, class: AppendageClass) -> Vec3 {
+        match class {
+            AppendageClass::Forelimb | AppendageClass::Hindlimb => Vec3::NEG_Y, // down
+            AppendageClass::Wing => Vec3::X, // outward (will be mirrored)
+            AppendageClass::Tail => self.config.bone_axis, // backward
+            AppendageClass::Tentacle => Vec3::NEG_Y, // down/out
+            AppendageClass::Antenna => Vec3::Y, // up
+        }
+    }
+
+    /// Calculate lateral spread for branching structures
+    fn branch_spread_offset(&self, index: u8, total: u8) -> f32 {
+        if total <= 1 {
+            return 0.0;
+        }
+        let normalized = index as f32 / (total - 1) as f32; // 0 to 1
+        let centered = normalized - 0.5; // -0.5 to 0.5
+        centered * 0.3 // scale factor
+    }
+}
+
+/// Mark every bone of a subtree as part of one radial fold
+fn tag_fold(node: &mut SkeletonNode, fold: u8) {
+    node.id.fold = Some(fold);
+    for child in &mut node.children {
+        tag_fold(child, fold);
+    }
+}
+
+/// Mark every bone of a subtree as growing on one vertebra
+fn tag_vertebra(node: &mut SkeletonNode, vertebra: u8) {
+    node.id.vertebra = Some(vertebra);
+    for child in &mut node.children {
+        tag_vertebra(child, vertebra);
+    }
+}
+
+/// Mark every bone of a subtree as growing from one socket
+fn tag_socket(node: &mut SkeletonNode, socket: u8) {
+    node.id.socket = Some(socket);
+    for child in &mut node.children {
+        tag_socket(child, socket);
+    }
+}
+
+/// The part of a horn's tissue between `from` and `to` along it, with the
+/// radius tapering linearly across the segment. Horns carry no muscle.
+fn horn_segment_tissue(tissue: &TissueEnvelope, from: f32, to: f32) -> TissueEnvelope {
+    let point = |t: f32, along: f32| CurvePoint {
+        t: Normalized::new(t).unwrap(),
+        value: tissue.radius_curve.evaluate(along),
+    };
+    TissueEnvelope {
+        profile: tissue.profile.clone(),
+        radius_curve: Curve::new(vec![point(0.0, from), point(1.0, to)]).unwrap(),
+        musculature: Vec::new(),
+    }
+}
+
+/// Rotation taking unit vector `from` onto `to`. Opposite directions have no
+/// single shortest arc, so they turn half a revolution about
+/// [`LATERAL_AXIS`], or [`BODY_AXIS`] for bones lying along the lateral axis.
+fn arc(from: Vec3, to: Vec3) -> Quat {
+    if from.dot(to) > -1.0 + 1e-6 {
+        return Quat::from_rotation_arc(from, to);
+    }
+    let pivot = LATERAL_AXIS.reject_from_normalized(from);
+    let pivot = if pivot.length_squared() > 1e-6 {
+        pivot
+    } else {
+        BODY_AXIS.reject_from_normalized(from)
+    };
+    Quat::from_axis_angle(pivot.normalize(), std::f32::consts::PI)
+}
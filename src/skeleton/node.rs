@@ -1,11 +1,12 @@
 use bevy::prelude::{Quat, Vec3};
 
-use crate::{primitives::Length, skeletal::JointArticulation};
+use crate::{primitives::Length, skeletal::JointArticulation, tissue_muscle::TissueEnvelope};
 
 use super::BoneId;
 
 /// Rest pose transform relative to parent bone
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RestTransform {
     pub translation: Vec3,
     pub rotation: Quat,
@@ -41,6 +42,11 @@ impl RestTransform {
         self
     }
 
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
     /// Mirror across the YZ plane (negate X)
     pub fn mirrored_x(mut self) -> Self {
         self.translation.x = -self.translation.x;
@@ -50,10 +56,15 @@ impl RestTransform {
 
 /// A node in the skeleton hierarchy
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SkeletonNode {
     pub id: BoneId,
     pub rest: RestTransform,
     pub length: Length,
+    /// Direction the bone extends in its own space
+    pub axis: Vec3,
+    /// Soft tissue wrapped around the bone, if it has any
+    pub tissue: Option<TissueEnvelope>,
     pub articulation: Option<JointArticulation>,
     pub children: Vec<SkeletonNode>,
 }
@@ -64,6 +75,8 @@ impl SkeletonNode {
             id,
             rest: RestTransform::default(),
             length,
+            axis: Vec3::NEG_Z,
+            tissue: None,
             articulation: None,
             children: Vec::new(),
         }
@@ -74,6 +87,16 @@ impl SkeletonNode {
         self
     }
 
+    pub fn with_axis(mut self, axis: Vec3) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    pub fn with_tissue(mut self, tissue: TissueEnvelope) -> Self {
+        self.tissue = Some(tissue);
+        self
+    }
+
     pub fn with_articulation(mut self, articulation: JointArticulation) -> Self {
         self.articulation = Some(articulation);
         self
@@ -142,6 +165,7 @@ impl<'a> Iterator for DepthFirstIter<'a> {
 
 /// The complete generated skeleton
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GeneratedSkeleton {
     pub root: SkeletonNode,
 }
@@ -1,8 +1,11 @@
-use bevy::math::Dir3;
+use std::f32::consts::TAU;
+
+use bevy::math::{Dir3, Quat, Vec3};
 
 use crate::primitives::{Count, LocalPosition};
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Socket<T> {
     pub position: LocalPosition,
     pub normal: Dir3,
@@ -10,18 +13,23 @@ pub struct Socket<T> {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BilateralPair<T> {
     pub left: T,
     pub right: T,
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SymmetricSocket<T> {
     Medial(Socket<T>),
     Lateral(BilateralPair<Socket<T>>),
+    /// One socket per fold of a radial body, in order around the body axis
+    Radial(Vec<Socket<T>>),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BodySymmetry {
     #[default]
     Bilateral,
@@ -30,3 +38,34 @@ pub enum BodySymmetry {
     },
     Asymmetric,
 }
+
+impl BodySymmetry {
+    /// Times each radial socket repeats around the body axis; `None` unless
+    /// the body is radial
+    pub fn fold_count(&self) -> Option<Count> {
+        match self {
+            BodySymmetry::Radial { fold_count } => Some(*fold_count),
+            _ => None,
+        }
+    }
+}
+
+/// Axis radial sockets repeat around, in the frame socket positions and
+/// normals are given in; the spine runs along it
+pub const BODY_AXIS: Vec3 = Vec3::NEG_Z;
+
+/// Axis left and right sockets mirror across, in the same frame; right
+/// sockets are their left partners reflected through the plane normal to it
+pub const LATERAL_AXIS: Vec3 = Vec3::X;
+
+/// Reflect a left-side position or direction onto the right side
+pub fn mirror_lateral(v: Vec3) -> Vec3 {
+    v.reflect(LATERAL_AXIS)
+}
+
+/// Rotation of one fold about `axis`, with the folds spread evenly around it
+/// and fold 0 unrotated
+pub fn fold_rotation(axis: Vec3, fold: u8, fold_count: u8) -> Quat {
+    let angle = TAU * fold as f32 / fold_count.max(1) as f32;
+    Quat::from_axis_angle(axis.normalize(), angle)
+}
@@ -0,0 +1,278 @@
+use bevy::prelude::LinearRgba;
+use rand::Rng;
+
+use crate::{
+    mutation::{SpeciesMutationRates, roll},
+    primitives::*,
+    sockets_symmetry::BodySymmetry,
+    species::{
+        AppendageSocketRule, BoneGenes, FeatureSocketRule, IntegumentGenes, SideGenes, Species,
+        SymmetricPlacement, UnvalidatedSpecies,
+    },
+    validation_errors::SpeciesValidationErrors,
+};
+
+/// How far a daughter species drifts from its parent
+#[derive(Clone, Debug)]
+pub struct DivergenceConfig {
+    /// Range, allowed list and `required` changes, as for
+    /// [`UnvalidatedSpecies::mutate`]
+    pub mutation: SpeciesMutationRates,
+    /// Chance to move each bone length and color range
+    pub shift: Normalized,
+    /// Largest shift, as a fraction of the range's centre
+    pub shift_step: Normalized,
+    /// Chance to add an appendage socket, and separately a feature socket,
+    /// copied from an existing rule onto another vertebra
+    pub new_socket: Normalized,
+    /// Chance to drop each optional spinal socket rule
+    pub removed_socket: Normalized,
+    /// Chance to switch to a different body symmetry
+    pub symmetry: Normalized,
+    /// Name for the daughter; derived from the parent's name and the seed if unset
+    pub name: Option<String>,
+}
+
+impl Default for DivergenceConfig {
+    fn default() -> Self {
+        Self {
+            mutation: SpeciesMutationRates::default(),
+            shift: Normalized::new(0.3).unwrap(),
+            shift_step: Normalized::new(0.2).unwrap(),
+            new_socket: Normalized::new(0.2).unwrap(),
+            removed_socket: Normalized::new(0.1).unwrap(),
+            symmetry: Normalized::new(0.05).unwrap(),
+            name: None,
+        }
+    }
+}
+
+/// Fold counts a species can take on when it becomes radially symmetric
+const RADIAL_FOLD_MIN: u8 = 3;
+const RADIAL_FOLD_MAX: u8 = 6;
+
+impl Species {
+    /// Branch a daughter species off this one.
+    ///
+    /// The daughter records this species' name as its
+    /// [`parent`](Species::parent) and is validated with [`Species::new`]
+    /// before it is returned. The same config and seed always give the same
+    /// daughter.
+    pub fn diverge(
+        &self,
+        config: &DivergenceConfig,
+        seed: GenomeSeed,
+    ) -> Result<Species, SpeciesValidationErrors> {
+        let mut genes = UnvalidatedSpecies::from(self.clone())
+            .mutate(&config.mutation, seed.derive("mutation"));
+        let mut rng: GenomeRng = seed.derive("divergence").into();
+
+        shift_bone(&mut genes.head.bone, config, &mut rng);
+        shift_integument(&mut genes.head.integument, config, &mut rng);
+        shift_bone(&mut genes.torso.spine.vertebra, config, &mut rng);
+        shift_integument(&mut genes.torso.integument, config, &mut rng);
+        for rule in &mut genes.torso.spine.appendage_sockets {
+            for appendage in rule.allowed.values_mut() {
+                shift_bone(&mut appendage.limb.segment, config, &mut rng);
+                shift_integument(&mut appendage.integument, config, &mut rng);
+            }
+        }
+
+        let spine = &mut genes.torso.spine;
+        spine
+            .appendage_sockets
+            .retain(|rule| rule.required || !roll(config.removed_socket, &mut rng));
+        spine
+            .feature_sockets
+            .retain(|rule| rule.required || !roll(config.removed_socket, &mut rng));
+
+        let max_vertebrae = spine.vertebra_count.end().value();
+        if roll(config.new_socket, &mut rng)
+            && let Some(rule) =
+                copy_to_free_vertebra(&spine.appendage_sockets, max_vertebrae, &mut rng)
+        {
+            spine.appendage_sockets.push(rule);
+        }
+        if roll(config.new_socket, &mut rng)
+            && let Some(rule) =
+                copy_to_free_vertebra(&spine.feature_sockets, max_vertebrae, &mut rng)
+        {
+            spine.feature_sockets.push(rule);
+        }
+
+        if roll(config.symmetry, &mut rng) {
+            let radial = any_placement(&genes, |p| matches!(p, SymmetricPlacement::Radial { .. }));
+            let uneven = genes
+                .torso
+                .spine
+                .appendage_sockets
+                .iter()
+                .any(|rule| !matches!(rule.sides, SideGenes::Mirrored));
+            genes.symmetry = other_symmetry(genes.symmetry, radial, uneven, &mut rng);
+        }
+
+        genes.name = config
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("{}-{:08x}", self.name(), seed.derive("name").0 as u32));
+        genes.parent = Some(self.name().to_string());
+
+        Species::new(genes)
+    }
+}
+
+/// A copy of a random rule on one vertebra the rule does not already cover.
+/// New sockets start out optional.
+fn copy_to_free_vertebra<R: Clone + SpinalRule>(
+    rules: &[R],
+    max_vertebrae: u8,
+    rng: &mut GenomeRng,
+) -> Option<R> {
+    if rules.is_empty() {
+        return None;
+    }
+    let template = &rules[rng.random_range(0..rules.len())];
+    let free: Vec<u8> = (0..max_vertebrae)
+        .filter(|&index| template.vertebra_indices().iter().all(|idx| idx.0 != index))
+        .collect();
+    if free.is_empty() {
+        return None;
+    }
+
+    let mut rule = template.clone();
+    rule.relocate(VertebraIndex(free[rng.random_range(0..free.len())]));
+    Some(rule)
+}
+
+/// Socket rules placed along the spine
+trait SpinalRule {
+    fn vertebra_indices(&self) -> &NonEmpty<VertebraIndex>;
+
+    /// Move the rule onto a single vertebra as an optional socket
+    fn relocate(&mut self, vertebra: VertebraIndex);
+}
+
+impl SpinalRule for AppendageSocketRule {
+    fn vertebra_indices(&self) -> &NonEmpty<VertebraIndex> {
+        &self.vertebra_indices
+    }
+
+    fn relocate(&mut self, vertebra: VertebraIndex) {
+        self.vertebra_indices = NonEmpty::new(vertebra);
+        self.required = false;
+    }
+}
+
+impl SpinalRule for FeatureSocketRule {
+    fn vertebra_indices(&self) -> &NonEmpty<VertebraIndex> {
+        &self.vertebra_indices
+    }
+
+    fn relocate(&mut self, vertebra: VertebraIndex) {
+        self.vertebra_indices = NonEmpty::new(vertebra);
+        self.required = false;
+    }
+}
+
+/// A different symmetry; only radial if any socket is, and only asymmetric
+/// if any socket's sides differ, since [`Species::new`] rejects the others.
+/// If none fits, a radial body draws a new fold count and any other keeps
+/// its symmetry.
+fn other_symmetry(
+    current: BodySymmetry,
+    radial_sockets: bool,
+    uneven_sides: bool,
+    rng: &mut GenomeRng,
+) -> BodySymmetry {
+    let radial = BodySymmetry::Radial {
+        fold_count: Count::new(rng.random_range(RADIAL_FOLD_MIN..=RADIAL_FOLD_MAX)),
+    };
+    let mut options = match current {
+        BodySymmetry::Bilateral => vec![radial, BodySymmetry::Asymmetric],
+        BodySymmetry::Radial { .. } => vec![BodySymmetry::Bilateral, BodySymmetry::Asymmetric],
+        BodySymmetry::Asymmetric => vec![BodySymmetry::Bilateral, radial],
+    };
+    if radial_sockets {
+        options.retain(|symmetry| symmetry.fold_count().is_some());
+    }
+    if uneven_sides {
+        options.retain(|symmetry| *symmetry == BodySymmetry::Asymmetric);
+    }
+    if options.is_empty() {
+        return if current.fold_count().is_some() {
+            radial
+        } else {
+            current
+        };
+    }
+    options[rng.random_range(0..options.len())]
+}
+
+fn any_placement(
+    genes: &UnvalidatedSpecies,
+    predicate: impl Fn(&SymmetricPlacement) -> bool,
+) -> bool {
+    let mut placements = genes
+        .head
+        .sensory_sockets
+        .iter()
+        .map(|rule| &rule.placement)
+        .chain(
+            genes
+                .head
+                .feature_sockets
+                .iter()
+                .map(|rule| &rule.placement),
+        )
+        .chain(
+            genes
+                .torso
+                .spine
+                .appendage_sockets
+                .iter()
+                .map(|rule| &rule.placement),
+        )
+        .chain(
+            genes
+                .torso
+                .spine
+                .feature_sockets
+                .iter()
+                .map(|rule| &rule.placement),
+        );
+    placements.any(predicate)
+}
+
+/// Move a range by up to `shift_step` of its centre, keeping its width
+fn shift_bone(genes: &mut BoneGenes, config: &DivergenceConfig, rng: &mut GenomeRng) {
+    if roll(config.shift, rng) {
+        let range = &genes.length;
+        let centre = (range.min.value() + range.max.value()) / 2.0;
+        let offset = rng.random_range(-1.0..=1.0) * config.shift_step.value() * centre;
+        if let (Some(min), Some(max)) = (
+            Length::new(range.min.value() + offset),
+            Length::new(range.max.value() + offset),
+        ) {
+            genes.length = ValueRange { min, max };
+        }
+    }
+}
+
+/// Move a color range by the same amount on each channel's bounds
+fn shift_integument(genes: &mut IntegumentGenes, config: &DivergenceConfig, rng: &mut GenomeRng) {
+    if roll(config.shift, rng) {
+        let step = config.shift_step.value();
+        let mut offset = || rng.random_range(-1.0..=1.0) * step;
+        let offset = LinearRgba::rgb(offset(), offset(), offset());
+        let shift = |color: LinearRgba| LinearRgba {
+            red: (color.red + offset.red).clamp(0.0, 1.0),
+            green: (color.green + offset.green).clamp(0.0, 1.0),
+            blue: (color.blue + offset.blue).clamp(0.0, 1.0),
+            alpha: color.alpha,
+        };
+        genes.base_color = ValueRange {
+            min: shift(genes.base_color.min),
+            max: shift(genes.base_color.max),
+        };
+    }
+}
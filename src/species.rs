@@ -1,27 +1,41 @@
+use std::f32::consts::TAU;
+
 use crate::{
     anatomical_features::{AnatomicalFeature, MandibleStructure, SensoryOrgan},
-    appendage::{AppendageClass, Terminus},
-    body::Torso,
+    appendage::{
+        Appendage, AppendageClass, BranchPoint, LimbStructure, MembraneSpan, Patagium, Terminus,
+    },
+    body::{SpinalAttachment, Spine, Torso, Vertebra},
     head::Cranium,
+    mutation::vary_appendage,
     organism::Organism,
     primitives::*,
-    sockets_symmetry::BodySymmetry,
+    skeletal::{Bone, BoneSegment},
+    skeleton::Side,
+    sockets_symmetry::{
+        BODY_AXIS, BilateralPair, BodySymmetry, Socket, SymmetricSocket, fold_rotation,
+        mirror_lateral,
+    },
     surface::*,
-    tissue_muscle::MuscleIntensity,
-    validation_errors::{GenerationError, SpeciesValidationError},
+    tissue_muscle::{MuscleAttachment, MuscleBulge, MuscleIntensity, MuscleSpread, TissueEnvelope},
+    validation_errors::{
+        Constraint, GenerationError, SpeciesValidationError, SpeciesValidationErrors,
+    },
 };
-use bevy::prelude::{Dir3, LinearRgba};
+use bevy::prelude::{Asset, Dir3, LinearRgba, TypePath, Vec3};
 use rand::prelude::*;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntegumentGenes {
     pub base_color: ValueRange<LinearRgba>,
-    pub allowed_patterns: NonEmpty<SurfacePattern>,
+    pub allowed_patterns: WeightedChoice<SurfacePattern>,
     pub roughness: ValueRange<Roughness>,
     pub metallic: ValueRange<Metallic>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TissueEnvelopeGenes {
     pub profile: CrossSectionProfile,
     pub radius_range: ValueRange<Curve>,
@@ -29,21 +43,24 @@ pub struct TissueEnvelopeGenes {
     pub bulge_intensity: ValueRange<MuscleIntensity>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoneGenes {
     pub length: ValueRange<Length>,
     pub tissue: TissueEnvelopeGenes,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LimbGenes {
     pub segment_count: InclusiveRange<Count>,
     pub segment: BoneGenes,
-    pub allowed_termini: NonEmpty<Terminus>,
+    pub allowed_termini: WeightedChoice<Terminus>,
     pub branching_probability: Normalized,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AppendageGenes {
     pub class: AppendageClass,
     pub limb: LimbGenes,
@@ -51,51 +68,99 @@ pub struct AppendageGenes {
     pub integument: IntegumentGenes,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SocketPlacement {
     pub position: LocalPosition,
     pub normal: Dir3,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SymmetricPlacement {
     Medial(SocketPlacement),
-    Lateral { offset: LocalPosition, normal: Dir3 },
+    Lateral {
+        offset: LocalPosition,
+        normal: Dir3,
+    },
+    /// Placed once for the first fold and repeated around the body axis for
+    /// every fold of a [`BodySymmetry::Radial`] body
+    Radial {
+        offset: LocalPosition,
+        normal: Dir3,
+    },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AppendageSocketRule {
     pub vertebra_indices: NonEmpty<VertebraIndex>,
     pub placement: SymmetricPlacement,
-    pub allowed: NonEmpty<AppendageGenes>,
+    pub allowed: WeightedChoice<AppendageGenes>,
     pub required: bool,
+    /// How the right side of a lateral socket differs from the left
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sides: SideGenes,
+}
+
+impl AppendageSocketRule {
+    /// Genes the appendage on `side` of the socket samples from; `None` for
+    /// medial and radial sockets
+    pub fn allowed_on(&self, side: Option<Side>) -> &WeightedChoice<AppendageGenes> {
+        match (&self.sides, side) {
+            (SideGenes::Independent { right }, Some(Side::Right)) => right.as_ref(),
+            _ => &self.allowed,
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
+/// How the two sides of a lateral appendage socket relate. Only asymmetric
+/// bodies may tell their sides apart.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SideGenes {
+    /// Both sides carry the same appendage
+    #[default]
+    Mirrored,
+    /// The right side samples from its own genes, like the oversized claw of
+    /// a fiddler crab
+    Independent {
+        right: Box<WeightedChoice<AppendageGenes>>,
+    },
+    /// The right side copies the left, then each length and color moves by
+    /// up to `variation` of its gene's range
+    Varied { variation: Normalized },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FeatureSocketRule {
     pub vertebra_indices: NonEmpty<VertebraIndex>,
     pub placement: SymmetricPlacement,
-    pub allowed: NonEmpty<AnatomicalFeature>,
+    pub allowed: WeightedChoice<AnatomicalFeature>,
     pub required: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SensorySocketRule {
     pub placement: SymmetricPlacement,
-    pub allowed: NonEmpty<SensoryOrgan>,
+    pub allowed: WeightedChoice<SensoryOrgan>,
     pub required: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CraniumGenes {
     pub bone: BoneGenes,
     pub sensory_sockets: Vec<SensorySocketRule>,
-    pub mandible: Option<NonEmpty<MandibleStructure>>,
+    pub mandible: Option<WeightedChoice<MandibleStructure>>,
     pub feature_sockets: Vec<FeatureSocketRule>,
     pub integument: IntegumentGenes,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpineGenes {
     pub vertebra_count: InclusiveRange<Count>,
     pub vertebra: BoneGenes,
@@ -103,60 +168,83 @@ pub struct SpineGenes {
     pub feature_sockets: Vec<FeatureSocketRule>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TorsoGenes {
     pub spine: SpineGenes,
     pub base_tissue: TissueEnvelopeGenes,
     pub integument: IntegumentGenes,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnvalidatedSpecies {
     pub name: String,
+    /// Name of the species this one diverged from
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub parent: Option<String>,
     pub symmetry: BodySymmetry,
     pub head: CraniumGenes,
     pub torso: TorsoGenes,
 }
 
-#[derive(Clone, Debug)]
+/// A validated species.
+///
+/// Serializes with the same shape as [`UnvalidatedSpecies`]; deserializing
+/// runs [`Species::new`] so invalid designs are rejected.
+#[derive(Clone, Debug, Asset, TypePath)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Species {
     name: String,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    parent: Option<String>,
     symmetry: BodySymmetry,
     head: CraniumGenes,
     torso: TorsoGenes,
 }
 
-impl Species {
-    /// Validate and construct a Species from unvalidated input
-    pub fn new(input: UnvalidatedSpecies) -> Result<Self, SpeciesValidationError> {
-        let max_vertebrae = input.torso.spine.vertebra_count.end();
-
-        // Validate appendage socket indices
-        for socket_rule in &input.torso.spine.appendage_sockets {
-            for idx in socket_rule.vertebra_indices.iter() {
-                if idx.0 >= max_vertebrae.value() {
-                    return Err(SpeciesValidationError::SocketIndexOutOfBounds {
-                        socket_index: *idx,
-                        max_vertebrae,
-                    });
-                }
-            }
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Species {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let input = UnvalidatedSpecies::deserialize(deserializer)?;
+        Species::new(input).map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<Species> for UnvalidatedSpecies {
+    fn from(species: Species) -> Self {
+        Self {
+            name: species.name,
+            parent: species.parent,
+            symmetry: species.symmetry,
+            head: species.head,
+            torso: species.torso,
         }
+    }
+}
 
-        // Validate feature socket indices
-        for socket_rule in &input.torso.spine.feature_sockets {
-            for idx in socket_rule.vertebra_indices.iter() {
-                if idx.0 >= max_vertebrae.value() {
-                    return Err(SpeciesValidationError::SocketIndexOutOfBounds {
-                        socket_index: *idx,
-                        max_vertebrae,
-                    });
-                }
-            }
+impl Species {
+    /// Validate and construct a Species from unvalidated input.
+    ///
+    /// The whole gene tree is checked and every problem is reported.
+    pub fn new(input: UnvalidatedSpecies) -> Result<Self, SpeciesValidationErrors> {
+        let mut validator = Validator {
+            symmetry: input.symmetry,
+            errors: Vec::new(),
+        };
+        validator.fold_count("symmetry");
+        validator.cranium("head", &input.head);
+        validator.torso("torso", &input.torso);
+        if !validator.errors.is_empty() {
+            return Err(SpeciesValidationErrors(validator.errors));
         }
 
         Ok(Self {
             name: input.name,
+            parent: input.parent,
             symmetry: input.symmetry,
             head: input.head,
             torso: input.torso,
@@ -166,6 +254,10 @@ impl Species {
     pub fn name(&self) -> &str {
         &self.name
     }
+    /// Name of the species this one diverged from, if any
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
     pub fn symmetry(&self) -> BodySymmetry {
         self.symmetry
     }
@@ -184,13 +276,17 @@ impl Species {
         self.torso.spine.vertebra_count.end()
     }
 
-    /// Generate a valid Organism from this Species using the given seed
+    /// Generate a valid Organism from this Species using the given seed.
+    ///
+    /// Every part draws from its own substream derived from the seed and the
+    /// part's path (see [`GenomeSeed::derive`]), so changing one gene only
+    /// reshuffles the parts that gene actually feeds.
     pub fn generate(&self, seed: GenomeSeed) -> Result<Organism, GenerationError> {
-        let mut rng: StdRng = seed.into();
+        let torso_seed = seed.derive("torso");
 
-        let vertebra_count = self.sample_vertebra_count(&mut rng);
-        let head = self.generate_cranium(&mut rng)?;
-        let torso = self.generate_torso(&mut rng, vertebra_count)?;
+        let vertebra_count = self.sample_vertebra_count(torso_seed.derive("vertebra_count"))?;
+        let head = self.generate_cranium(seed.derive("head"))?;
+        let torso = self.generate_torso(torso_seed, vertebra_count)?;
 
         Ok(Organism {
             genome_seed: seed,
@@ -200,19 +296,630 @@ impl Species {
         })
     }
 
-    fn sample_vertebra_count(&self, _rng: &mut impl Rng) -> Count {
-        todo!("Sample from vertebra_count range")
+    /// Draw a vertebra count from the part of the range that leaves room for
+    /// every required spinal socket
+    fn sample_vertebra_count(&self, seed: GenomeSeed) -> Result<Count, GenerationError> {
+        let range = self.torso.spine.vertebra_count;
+        // Longer spines only ever fit more sockets, so every count above the
+        // first that fits fits too
+        let Some(fewest) = range
+            .iter()
+            .find(|&count| self.unmet_spine_constraint(count).is_none())
+        else {
+            // An empty range has no spine to draw at all
+            let (rule, constraint) = self.unmet_spine_constraint(range.end()).unwrap_or((
+                "torso.spine.vertebra_count".to_string(),
+                Constraint::NonEmptySpine,
+            ));
+            return Err(GenerationError::ConstraintUnsatisfiable {
+                rule,
+                constraint,
+                attempts: 1,
+            });
+        };
+        Ok(InclusiveRange::new(fewest, range.end()).sample(&mut part_rng(seed)))
+    }
+
+    /// The first spinal constraint a spine of `vertebra_count` breaks
+    pub(crate) fn unmet_spine_constraint(
+        &self,
+        vertebra_count: Count,
+    ) -> Option<(String, Constraint)> {
+        let spine = &self.torso.spine;
+        if vertebra_count.value() == 0 {
+            return Some((
+                "torso.spine.vertebra_count".to_string(),
+                Constraint::NonEmptySpine,
+            ));
+        }
+
+        let rules = spine
+            .appendage_sockets
+            .iter()
+            .map(|rule| (&rule.vertebra_indices, rule.required))
+            .enumerate()
+            .map(|(i, rule)| (format!("torso.spine.appendage_sockets[{i}]"), rule));
+        let features = spine
+            .feature_sockets
+            .iter()
+            .map(|rule| (&rule.vertebra_indices, rule.required))
+            .enumerate()
+            .map(|(i, rule)| (format!("torso.spine.feature_sockets[{i}]"), rule));
+        rules
+            .chain(features)
+            .find(|(_, (indices, required))| {
+                *required && sockets_in_range(indices, vertebra_count).is_empty()
+            })
+            .map(|(path, (indices, _))| {
+                (
+                    path,
+                    Constraint::SocketWithinSpine {
+                        vertebra_index: *indices.first(),
+                    },
+                )
+            })
     }
 
-    fn generate_cranium(&self, _rng: &mut impl Rng) -> Result<Cranium, GenerationError> {
-        todo!("Generate cranium from CraniumGenes")
+    fn generate_cranium(&self, seed: GenomeSeed) -> Result<Cranium, GenerationError> {
+        let genes = &self.head;
+
+        let bone = generate_bone(&genes.bone, seed.derive("bone"))?;
+
+        let sensory_seed = seed.derive("sensory_sockets");
+        let sensory_sockets = genes
+            .sensory_sockets
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                fill_socket(
+                    &rule.placement,
+                    self.symmetry,
+                    &rule.allowed,
+                    rule.required,
+                    sensory_seed.derive_index(i as u64),
+                    |organ, _| Ok(organ.clone()),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mandible_socket = genes.mandible.as_ref().map(|allowed| Socket {
+            position: LocalPosition(MANDIBLE_SOCKET_POSITION),
+            normal: Dir3::NEG_Y,
+            attachment: Some(allowed.sample(&mut part_rng(seed.derive("mandible")))),
+        });
+
+        let feature_seed = seed.derive("feature_sockets");
+        let feature_sockets = genes
+            .feature_sockets
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                fill_socket(
+                    &rule.placement,
+                    self.symmetry,
+                    &rule.allowed,
+                    rule.required,
+                    feature_seed.derive_index(i as u64),
+                    |feature, _| Ok(feature.clone()),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Cranium {
+            bone,
+            sensory_sockets,
+            mandible_socket,
+            feature_sockets,
+            integument: generate_integument(&genes.integument, seed.derive("integument")),
+        })
     }
 
     fn generate_torso(
         &self,
-        _rng: &mut impl Rng,
-        _vertebra_count: Count,
+        seed: GenomeSeed,
+        vertebra_count: Count,
     ) -> Result<Torso, GenerationError> {
-        todo!("Generate torso from TorsoGenes")
+        let genes = &self.torso;
+        let spine_seed = seed.derive("spine");
+
+        let vertebra_seed = spine_seed.derive("vertebrae");
+        let vertebrae = (0..vertebra_count.value())
+            .map(|i| {
+                Ok(Vertebra {
+                    bone: generate_bone(
+                        &genes.spine.vertebra,
+                        vertebra_seed.derive_index(i as u64),
+                    )?,
+                })
+            })
+            .collect::<Result<Vec<_>, GenerationError>>()?;
+        let vertebrae = NonEmpty::from_vec(vertebrae)
+            .expect("vertebra count is checked before the torso is generated");
+
+        let appendage_seed = spine_seed.derive("appendage_sockets");
+        let mut appendages = Vec::new();
+        for (i, rule) in genes.spine.appendage_sockets.iter().enumerate() {
+            let indices = sockets_in_range(&rule.vertebra_indices, vertebra_count);
+            let rule_seed = appendage_seed.derive_index(i as u64);
+            for vertebra_index in indices {
+                let socket = fill_appendage_socket(
+                    rule,
+                    self.symmetry,
+                    rule_seed.derive_index(vertebra_index.0 as u64),
+                )?;
+                appendages.push(SpinalAttachment {
+                    vertebra_index,
+                    socket,
+                });
+            }
+        }
+
+        let feature_seed = spine_seed.derive("feature_sockets");
+        let mut features = Vec::new();
+        for (i, rule) in genes.spine.feature_sockets.iter().enumerate() {
+            let indices = sockets_in_range(&rule.vertebra_indices, vertebra_count);
+            let rule_seed = feature_seed.derive_index(i as u64);
+            for vertebra_index in indices {
+                let socket = fill_socket(
+                    &rule.placement,
+                    self.symmetry,
+                    &rule.allowed,
+                    rule.required,
+                    rule_seed.derive_index(vertebra_index.0 as u64),
+                    |feature, _| Ok(feature.clone()),
+                )?;
+                features.push(SpinalAttachment {
+                    vertebra_index,
+                    socket,
+                });
+            }
+        }
+
+        Ok(Torso {
+            spine: Spine {
+                vertebrae,
+                appendages,
+                features,
+            },
+            base_tissue: generate_tissue(&genes.base_tissue, seed.derive("base_tissue"))?,
+            integument: generate_integument(&genes.integument, seed.derive("integument")),
+        })
+    }
+}
+
+/// Walks a species' genes, collecting every problem with its path
+struct Validator {
+    symmetry: BodySymmetry,
+    errors: Vec<SpeciesValidationError>,
+}
+
+impl Validator {
+    fn cranium(&mut self, path: &str, genes: &CraniumGenes) {
+        self.bone(&format!("{path}.bone"), &genes.bone);
+        for (i, rule) in genes.sensory_sockets.iter().enumerate() {
+            let path = format!("{path}.sensory_sockets[{i}]");
+            self.placement(&format!("{path}.placement"), &rule.placement);
+            self.weights(&format!("{path}.allowed"), &rule.allowed);
+        }
+        if let Some(mandible) = &genes.mandible {
+            self.weights(&format!("{path}.mandible"), mandible);
+        }
+        for (i, rule) in genes.feature_sockets.iter().enumerate() {
+            let path = format!("{path}.feature_sockets[{i}]");
+            self.placement(&format!("{path}.placement"), &rule.placement);
+            self.weights(&format!("{path}.allowed"), &rule.allowed);
+        }
+        self.integument(&format!("{path}.integument"), &genes.integument);
+    }
+
+    fn torso(&mut self, path: &str, genes: &TorsoGenes) {
+        let spine = &genes.spine;
+        let count = &spine.vertebra_count;
+        if count.start() > count.end() || count.end().value() == 0 {
+            self.errors
+                .push(SpeciesValidationError::EmptyVertebraRange {
+                    path: format!("{path}.spine.vertebra_count"),
+                });
+        }
+        self.bone(&format!("{path}.spine.vertebra"), &spine.vertebra);
+
+        for (i, rule) in spine.appendage_sockets.iter().enumerate() {
+            let path = format!("{path}.spine.appendage_sockets[{i}]");
+            self.vertebra_indices(&path, &rule.vertebra_indices, count.end());
+            self.placement(&format!("{path}.placement"), &rule.placement);
+            self.weights(&format!("{path}.allowed"), &rule.allowed);
+            for (j, genes) in rule.allowed.values().enumerate() {
+                self.appendage(&format!("{path}.allowed[{j}]"), genes);
+            }
+            self.sides(&format!("{path}.sides"), &rule.placement, &rule.sides);
+        }
+        for (i, rule) in spine.feature_sockets.iter().enumerate() {
+            let path = format!("{path}.spine.feature_sockets[{i}]");
+            self.vertebra_indices(&path, &rule.vertebra_indices, count.end());
+            self.placement(&format!("{path}.placement"), &rule.placement);
+            self.weights(&format!("{path}.allowed"), &rule.allowed);
+        }
+
+        self.tissue(&format!("{path}.base_tissue"), &genes.base_tissue);
+        self.integument(&format!("{path}.integument"), &genes.integument);
+    }
+
+    fn appendage(&mut self, path: &str, genes: &AppendageGenes) {
+        let limb = &genes.limb;
+        self.count(&format!("{path}.limb.segment_count"), &limb.segment_count);
+        self.bone(&format!("{path}.limb.segment"), &limb.segment);
+        self.weights(
+            &format!("{path}.limb.allowed_termini"),
+            &limb.allowed_termini,
+        );
+        if limb.branching_probability.value() > 0.0 && limb.segment_count.end().value() == 0 {
+            self.errors
+                .push(SpeciesValidationError::BranchingWithoutSegments {
+                    path: format!("{path}.limb.branching_probability"),
+                });
+        }
+        self.integument(&format!("{path}.integument"), &genes.integument);
+    }
+
+    fn bone(&mut self, path: &str, genes: &BoneGenes) {
+        self.range(&format!("{path}.length"), &genes.length);
+        self.tissue(&format!("{path}.tissue"), &genes.tissue);
     }
+
+    fn tissue(&mut self, path: &str, genes: &TissueEnvelopeGenes) {
+        let radius = &genes.radius_range;
+        if radius.min.len() != radius.max.len() {
+            self.errors
+                .push(SpeciesValidationError::MismatchedCurvePoints {
+                    path: format!("{path}.radius_range"),
+                    min: radius.min.len(),
+                    max: radius.max.len(),
+                });
+        }
+        self.count(&format!("{path}.bulge_count"), &genes.bulge_count);
+        self.range(&format!("{path}.bulge_intensity"), &genes.bulge_intensity);
+    }
+
+    fn integument(&mut self, path: &str, genes: &IntegumentGenes) {
+        let ValueRange { min, max } = genes.base_color;
+        if min.red > max.red
+            || min.green > max.green
+            || min.blue > max.blue
+            || min.alpha > max.alpha
+        {
+            self.inverted(&format!("{path}.base_color"));
+        }
+        self.weights(&format!("{path}.allowed_patterns"), &genes.allowed_patterns);
+        self.range(&format!("{path}.roughness"), &genes.roughness);
+        self.range(&format!("{path}.metallic"), &genes.metallic);
+    }
+
+    fn vertebra_indices(&mut self, path: &str, indices: &NonEmpty<VertebraIndex>, max: Count) {
+        for (i, idx) in indices.iter().enumerate() {
+            if idx.0 >= max.value() {
+                self.errors
+                    .push(SpeciesValidationError::SocketIndexOutOfBounds {
+                        path: format!("{path}.vertebra_indices[{i}]"),
+                        socket_index: *idx,
+                        max_vertebrae: max,
+                    });
+            }
+        }
+    }
+
+    fn fold_count(&mut self, path: &str) {
+        if let Some(fold_count) = self.symmetry.fold_count()
+            && fold_count.value() < 2
+        {
+            self.errors.push(SpeciesValidationError::TooFewFolds {
+                path: path.to_string(),
+                fold_count,
+            });
+        }
+    }
+
+    fn placement(&mut self, path: &str, placement: &SymmetricPlacement) {
+        if matches!(placement, SymmetricPlacement::Radial { .. })
+            && self.symmetry.fold_count().is_none()
+        {
+            self.errors.push(SpeciesValidationError::RadialOnNonRadial {
+                path: path.to_string(),
+            });
+        }
+    }
+
+    fn sides(&mut self, path: &str, placement: &SymmetricPlacement, sides: &SideGenes) {
+        if matches!(sides, SideGenes::Mirrored) {
+            return;
+        }
+        if self.symmetry != BodySymmetry::Asymmetric
+            || !matches!(placement, SymmetricPlacement::Lateral { .. })
+        {
+            self.errors.push(SpeciesValidationError::UnevenSides {
+                path: path.to_string(),
+            });
+        }
+        if let SideGenes::Independent { right } = sides {
+            self.weights(&format!("{path}.right"), right);
+            for (i, genes) in right.values().enumerate() {
+                self.appendage(&format!("{path}.right[{i}]"), genes);
+            }
+        }
+    }
+
+    fn weights<T>(&mut self, path: &str, list: &WeightedChoice<T>) {
+        if list
+            .iter()
+            .any(|entry| !entry.weight.is_finite() || entry.weight < 0.0)
+        {
+            self.errors.push(SpeciesValidationError::InvalidWeights {
+                path: path.to_string(),
+            });
+        } else if !list.has_valid_weights() {
+            self.errors.push(SpeciesValidationError::EmptyAllowedList {
+                path: path.to_string(),
+            });
+        }
+    }
+
+    fn range<T: PartialOrd>(&mut self, path: &str, range: &ValueRange<T>) {
+        if range.min > range.max {
+            self.inverted(path);
+        }
+    }
+
+    fn count(&mut self, path: &str, range: &InclusiveRange<Count>) {
+        if range.start() > range.end() {
+            self.inverted(path);
+        }
+    }
+
+    fn inverted(&mut self, path: &str) {
+        self.errors.push(SpeciesValidationError::InvertedRange {
+            path: path.to_string(),
+        });
+    }
+}
+
+/// Probability that a socket which is not `required` receives an attachment
+const OPTIONAL_SOCKET_FILL_PROBABILITY: f64 = 0.5;
+
+/// Mandible attachment point relative to the cranium (below and slightly forward)
+const MANDIBLE_SOCKET_POSITION: Vec3 = Vec3::new(0.0, -0.2, 0.1);
+
+/// Muscle spread has no gene of its own
+const BULGE_SPREAD: ValueRange<f32> = ValueRange {
+    min: 0.05,
+    max: 0.25,
+};
+
+/// Bounds for the number of sub-limbs sprouting from a branch point
+const BRANCH_COUNT_MIN: u8 = 2;
+const BRANCH_COUNT_MAX: u8 = 3;
+
+const PATAGIUM_THICKNESS: f32 = 0.01;
+
+pub(crate) fn generate_appendage(
+    genes: &AppendageGenes,
+    seed: GenomeSeed,
+) -> Result<Appendage, GenerationError> {
+    let structure = generate_limb(&genes.limb, seed.derive("limb"), true)?;
+
+    let mut rng = part_rng(seed.derive("patagium"));
+    let patagium = (structure.segments.len() >= 2
+        && rng.random_bool(genes.patagium_probability.value() as f64))
+    .then(|| Patagium {
+        spans: vec![MembraneSpan {
+            proximal_bone: 0,
+            distal_bone: structure.segments.len() - 1,
+            thickness: Length::new(PATAGIUM_THICKNESS).unwrap(),
+            scallop_depth: Normalized::new(rng.random()).unwrap(),
+        }],
+    });
+
+    Ok(Appendage {
+        class: genes.class,
+        structure,
+        patagium,
+        integument: generate_integument(&genes.integument, seed.derive("integument")),
+    })
+}
+
+fn generate_limb(
+    genes: &LimbGenes,
+    seed: GenomeSeed,
+    allow_branching: bool,
+) -> Result<LimbStructure, GenerationError> {
+    let segment_count = genes
+        .segment_count
+        .sample(&mut part_rng(seed.derive("segment_count")));
+
+    let segment_seed = seed.derive("segments");
+    let segments = (0..segment_count.value())
+        .map(|i| {
+            Ok(BoneSegment {
+                bone: generate_bone(&genes.segment, segment_seed.derive_index(i as u64))?,
+                distal_joint: None,
+            })
+        })
+        .collect::<Result<Vec<_>, GenerationError>>()?;
+
+    // Branches never branch again, which keeps recursion bounded
+    let mut rng = part_rng(seed.derive("branching"));
+    let branching = if allow_branching
+        && !segments.is_empty()
+        && rng.random_bool(genes.branching_probability.value() as f64)
+    {
+        Some(BranchPoint {
+            parent_segment: rng.random_range(0..segments.len()),
+            branch_count: Count::new(rng.random_range(BRANCH_COUNT_MIN..=BRANCH_COUNT_MAX)),
+            branch: Box::new(generate_limb(genes, seed.derive("branch"), false)?),
+        })
+    } else {
+        None
+    };
+
+    Ok(LimbStructure {
+        segments,
+        branching,
+        terminus: genes
+            .allowed_termini
+            .sample(&mut part_rng(seed.derive("terminus"))),
+    })
+}
+
+pub(crate) fn generate_bone(genes: &BoneGenes, seed: GenomeSeed) -> Result<Bone, GenerationError> {
+    Ok(Bone {
+        length: genes.length.sample(&mut part_rng(seed.derive("length"))),
+        tissue: generate_tissue(&genes.tissue, seed.derive("tissue"))?,
+    })
+}
+
+fn generate_tissue(
+    genes: &TissueEnvelopeGenes,
+    seed: GenomeSeed,
+) -> Result<TissueEnvelope, GenerationError> {
+    let radius_range = &genes.radius_range;
+    if radius_range.min.len() != radius_range.max.len() {
+        return Err(GenerationError::ConstraintUnsatisfiable {
+            rule: "tissue.radius_range".to_string(),
+            constraint: Constraint::MatchingCurvePoints {
+                min: radius_range.min.len(),
+                max: radius_range.max.len(),
+            },
+            attempts: 1,
+        });
+    }
+
+    let mut rng = part_rng(seed.derive("musculature"));
+    let musculature = (0..genes.bulge_count.sample(&mut rng).value())
+        .map(|_| MuscleBulge {
+            attachment: MuscleAttachment {
+                position: Normalized::new(rng.random()).unwrap(),
+                radial_angle: Radians::new(rng.random_range(0.0..TAU)),
+            },
+            intensity: genes.bulge_intensity.sample(&mut rng),
+            spread: MuscleSpread::new(BULGE_SPREAD.sample(&mut rng)).unwrap(),
+        })
+        .collect();
+
+    Ok(TissueEnvelope {
+        profile: genes.profile.clone(),
+        radius_curve: radius_range.sample(&mut part_rng(seed.derive("radius"))),
+        musculature,
+    })
+}
+
+fn generate_integument(genes: &IntegumentGenes, seed: GenomeSeed) -> Integument {
+    let mut rng = part_rng(seed);
+    Integument {
+        base_color: genes.base_color.sample(&mut rng),
+        pattern: genes.allowed_patterns.sample(&mut rng),
+        roughness: genes.roughness.sample(&mut rng),
+        metallic: genes.metallic.sample(&mut rng),
+    }
+}
+
+/// Build a socket from its placement, filling it when required or by chance.
+/// Every side or fold gets the same attachment.
+pub(crate) fn fill_socket<G, T: Clone>(
+    placement: &SymmetricPlacement,
+    symmetry: BodySymmetry,
+    allowed: &WeightedChoice<G>,
+    required: bool,
+    seed: GenomeSeed,
+    mut build: impl FnMut(&G, GenomeSeed) -> Result<T, GenerationError>,
+) -> Result<SymmetricSocket<T>, GenerationError> {
+    let mut rng = part_rng(seed.derive("fill"));
+    let attachment = if required || rng.random_bool(OPTIONAL_SOCKET_FILL_PROBABILITY) {
+        let genes = allowed.choose(&mut rng);
+        Some(build(genes, seed.derive("attachment"))?)
+    } else {
+        None
+    };
+
+    Ok(match placement {
+        SymmetricPlacement::Medial(socket) => SymmetricSocket::Medial(Socket {
+            position: socket.position,
+            normal: socket.normal,
+            attachment,
+        }),
+        SymmetricPlacement::Lateral { offset, normal } => SymmetricSocket::Lateral(BilateralPair {
+            left: Socket {
+                position: *offset,
+                normal: *normal,
+                attachment: attachment.clone(),
+            },
+            right: Socket {
+                position: LocalPosition(mirror_lateral(offset.as_vec3())),
+                normal: Dir3::new_unchecked(mirror_lateral(normal.as_vec3())),
+                attachment,
+            },
+        }),
+        SymmetricPlacement::Radial { offset, normal } => {
+            let fold_count = symmetry.fold_count().map_or(1, Count::value);
+            SymmetricSocket::Radial(
+                (0..fold_count)
+                    .map(|fold| {
+                        let rotation = fold_rotation(BODY_AXIS, fold, fold_count);
+                        Socket {
+                            position: LocalPosition(rotation * offset.as_vec3()),
+                            normal: rotation * *normal,
+                            attachment: attachment.clone(),
+                        }
+                    })
+                    .collect(),
+            )
+        }
+    })
+}
+
+/// Fill an appendage socket; the right side of a lateral pair then gets its
+/// own appendage if the rule's sides differ
+pub(crate) fn fill_appendage_socket(
+    rule: &AppendageSocketRule,
+    symmetry: BodySymmetry,
+    seed: GenomeSeed,
+) -> Result<SymmetricSocket<Appendage>, GenerationError> {
+    let mut socket = fill_socket(
+        &rule.placement,
+        symmetry,
+        &rule.allowed,
+        rule.required,
+        seed,
+        generate_appendage,
+    )?;
+    if let SymmetricSocket::Lateral(pair) = &mut socket
+        && let Some(left) = &pair.left.attachment
+    {
+        let seed = seed.derive("right");
+        match &rule.sides {
+            SideGenes::Mirrored => {}
+            SideGenes::Independent { right } => {
+                let genes = right.choose(&mut part_rng(seed.derive("fill")));
+                pair.right.attachment = Some(generate_appendage(genes, seed.derive("attachment"))?);
+            }
+            SideGenes::Varied { variation } => {
+                pair.right.attachment = Some(vary_appendage(left, &rule.allowed, *variation, seed));
+            }
+        }
+    }
+    Ok(socket)
+}
+
+/// The random stream for a single organism part
+pub(crate) fn part_rng(seed: GenomeSeed) -> GenomeRng {
+    seed.into()
+}
+
+/// Socket indices from a rule that fit within the sampled spine
+fn sockets_in_range(
+    indices: &NonEmpty<VertebraIndex>,
+    vertebra_count: Count,
+) -> Vec<VertebraIndex> {
+    indices
+        .iter()
+        .copied()
+        .filter(|idx| idx.0 < vertebra_count.value())
+        .collect()
 }
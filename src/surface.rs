@@ -1,13 +1,19 @@
 use crate::primitives::*;
 use bevy::prelude::*;
+use rand::Rng;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Roughness(pub Normalized);
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Metallic(pub Normalized);
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SurfacePattern {
     Smooth,
     Scaled,
@@ -18,9 +24,38 @@ pub enum SurfacePattern {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Integument {
     pub base_color: LinearRgba,
     pub pattern: SurfacePattern,
     pub roughness: Roughness,
     pub metallic: Metallic,
 }
+
+impl Sample for ValueRange<Roughness> {
+    type Output = Roughness;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Roughness {
+        Roughness(
+            ValueRange {
+                min: self.min.0,
+                max: self.max.0,
+            }
+            .sample(rng),
+        )
+    }
+}
+
+impl Sample for ValueRange<Metallic> {
+    type Output = Metallic;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Metallic {
+        Metallic(
+            ValueRange {
+                min: self.min.0,
+                max: self.max.0,
+            }
+            .sample(rng),
+        )
+    }
+}
@@ -1,11 +1,15 @@
 use crate::primitives::*;
+use rand::Rng;
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MuscleAttachment {
     pub position: Normalized,
     pub radial_angle: Radians,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f32", into = "f32"))]
 pub struct MuscleIntensity(f32);
 
 impl MuscleIntensity {
@@ -18,6 +22,8 @@ impl MuscleIntensity {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f32", into = "f32"))]
 pub struct MuscleSpread(f32);
 
 impl MuscleSpread {
@@ -29,16 +35,61 @@ impl MuscleSpread {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MuscleBulge {
     pub attachment: MuscleAttachment,
     pub intensity: MuscleIntensity,
     pub spread: MuscleSpread,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TissueEnvelope {
     pub profile: CrossSectionProfile,
     pub radius_curve: Curve,
     pub musculature: Vec<MuscleBulge>,
 }
+
+impl Sample for ValueRange<MuscleIntensity> {
+    type Output = MuscleIntensity;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> MuscleIntensity {
+        MuscleIntensity::new(lerp(self.min.value(), self.max.value(), rng))
+            .expect("interpolating between non-negative intensities stays non-negative")
+    }
+}
+
+impl TryFrom<f32> for MuscleIntensity {
+    type Error = InvalidValue;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(InvalidValue {
+            type_name: "MuscleIntensity",
+            value,
+        })
+    }
+}
+
+impl From<MuscleIntensity> for f32 {
+    fn from(value: MuscleIntensity) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<f32> for MuscleSpread {
+    type Error = InvalidValue;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(InvalidValue {
+            type_name: "MuscleSpread",
+            value,
+        })
+    }
+}
+
+impl From<MuscleSpread> for f32 {
+    fn from(value: MuscleSpread) -> Self {
+        value.0
+    }
+}
@@ -1,34 +1,397 @@
-use crate::{appendage::AppendageClass, primitives::*};
+use std::fmt;
+
+use crate::{appendage::AppendageClass, primitives::*, sockets_symmetry::BodySymmetry};
+
+/// One problem in an [`UnvalidatedSpecies`](crate::UnvalidatedSpecies).
+///
+/// `path` locates the offending gene, such as
+/// `torso.spine.appendage_sockets[2].allowed[0].limb.segment.length`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SpeciesValidationError {
-    EmptyVertebraRange,
+    /// The vertebra count range is inverted or allows only zero vertebrae
+    EmptyVertebraRange { path: String },
     SocketIndexOutOfBounds {
+        path: String,
         socket_index: VertebraIndex,
         max_vertebrae: Count,
     },
-    EmptyAllowedList {
-        context: String,
+    /// Every weight is zero, so nothing can ever be chosen
+    EmptyAllowedList { path: String },
+    /// Weights must be finite and non-negative
+    InvalidWeights { path: String },
+    /// A range whose minimum is above its maximum
+    InvertedRange { path: String },
+    /// Sides that differ on a socket that is not lateral, or on a body that
+    /// is not asymmetric
+    UnevenSides { path: String },
+    /// A radial socket on a body plan without folds
+    RadialOnNonRadial { path: String },
+    /// A radial body needs at least two folds
+    TooFewFolds { path: String, fold_count: Count },
+    /// A chance to branch on a limb that can have no segments
+    BranchingWithoutSegments { path: String },
+    /// Curve bounds with different numbers of points
+    MismatchedCurvePoints {
+        path: String,
+        min: usize,
+        max: usize,
     },
 }
 
+/// Every problem found while validating a species; never empty
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpeciesValidationErrors(pub(crate) Vec<SpeciesValidationError>);
+
+impl SpeciesValidationErrors {
+    pub fn errors(&self) -> &[SpeciesValidationError] {
+        &self.0
+    }
+}
+
+impl IntoIterator for SpeciesValidationErrors {
+    type Item = SpeciesValidationError;
+    type IntoIter = std::vec::IntoIter<SpeciesValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// One way an [`UnvalidatedOrganism`](crate::organism::UnvalidatedOrganism)
+/// could not have come from its species.
+///
+/// `path` locates the offending part, such as
+/// `torso.spine.appendages[1].left.structure.terminus`. For a required socket
+/// the organism lacks entirely, it points at the species' socket rule instead.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum OrganismValidationError {
     VertebraIndexOutOfBounds {
+        path: String,
         index: VertebraIndex,
         vertebra_count: usize,
     },
+    VertebraCountOutOfRange {
+        count: usize,
+        min: Count,
+        max: Count,
+    },
+    SymmetryMismatch {
+        expected: BodySymmetry,
+        found: BodySymmetry,
+    },
+    /// A socket that no rule of the species accounts for
+    UnexpectedSocket {
+        path: String,
+    },
+    /// A socket whose medial, lateral or radial placement differs from its
+    /// rule's
+    PlacementMismatch {
+        path: String,
+    },
+    /// A radial socket with a different number of folds than the body
+    FoldCountMismatch {
+        path: String,
+        expected: usize,
+        found: usize,
+    },
+    RequiredSocketEmpty {
+        path: String,
+    },
+    /// None of the socket's allowed appendages has this class
     AppendageClassMismatch {
-        expected: AppendageClass,
+        path: String,
         found: AppendageClass,
     },
-    RequiredSocketEmpty {
-        vertebra_index: VertebraIndex,
+    /// A terminus, pattern, feature, organ or structure the genes never produce
+    NotAllowed {
+        path: String,
+    },
+    /// A length, color or count outside the species' range
+    OutOfRange {
+        path: String,
     },
-    MissingHead,
 }
 
-#[derive(Clone, Debug)]
+/// Every problem found while validating an organism; never empty
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrganismValidationErrors(pub(crate) Vec<OrganismValidationError>);
+
+impl OrganismValidationErrors {
+    pub fn errors(&self) -> &[OrganismValidationError] {
+        &self.0
+    }
+}
+
+impl IntoIterator for OrganismValidationErrors {
+    type Item = OrganismValidationError;
+    type IntoIter = std::vec::IntoIter<OrganismValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GenerationError {
     InvalidSeed,
-    ConstraintUnsatisfiable { context: String },
+    /// The genes demand something generation could not produce
+    ConstraintUnsatisfiable {
+        /// Path of the gene that imposed the constraint, such as
+        /// `torso.spine.appendage_sockets[1]`
+        rule: String,
+        constraint: Constraint,
+        /// Draws made before giving up
+        attempts: u32,
+    },
+}
+
+/// A requirement of a species' genes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// The spine needs at least one vertebra
+    NonEmptySpine,
+    /// A required socket needs its vertebra to exist
+    SocketWithinSpine { vertebra_index: VertebraIndex },
+    /// Both bounds of a curve range need the same number of points
+    MatchingCurvePoints { min: usize, max: usize },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PhylogenyError {
+    /// A species with this name is already recorded
+    DuplicateSpecies { name: String },
+    /// The species' parent has not been recorded yet
+    UnknownParent { name: String },
+}
+
+#[derive(Clone, Debug)]
+pub enum PopulationError {
+    /// A population needs at least one organism per species
+    EmptyPopulation,
+    Generation(GenerationError),
+    Organism(OrganismValidationErrors),
+    Species(SpeciesValidationErrors),
+    Phylogeny(PhylogenyError),
+}
+
+impl From<GenerationError> for PopulationError {
+    fn from(err: GenerationError) -> Self {
+        Self::Generation(err)
+    }
+}
+
+impl From<OrganismValidationErrors> for PopulationError {
+    fn from(err: OrganismValidationErrors) -> Self {
+        Self::Organism(err)
+    }
+}
+
+impl From<SpeciesValidationErrors> for PopulationError {
+    fn from(err: SpeciesValidationErrors) -> Self {
+        Self::Species(err)
+    }
+}
+
+impl From<PhylogenyError> for PopulationError {
+    fn from(err: PhylogenyError) -> Self {
+        Self::Phylogeny(err)
+    }
+}
+
+impl fmt::Display for SpeciesValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyVertebraRange { path } => write!(f, "{path}: allows no vertebrae"),
+            Self::SocketIndexOutOfBounds {
+                path,
+                socket_index,
+                max_vertebrae,
+            } => write!(
+                f,
+                "{path}: vertebra {} is past the last of at most {} vertebrae",
+                socket_index.0,
+                max_vertebrae.value()
+            ),
+            Self::EmptyAllowedList { path } => {
+                write!(f, "{path}: every weight is zero, so nothing can be chosen")
+            }
+            Self::InvalidWeights { path } => {
+                write!(f, "{path}: weights must be finite and non-negative")
+            }
+            Self::InvertedRange { path } => write!(f, "{path}: minimum is above maximum"),
+            Self::UnevenSides { path } => write!(
+                f,
+                "{path}: sides can only differ on lateral sockets of asymmetric bodies"
+            ),
+            Self::RadialOnNonRadial { path } => {
+                write!(f, "{path}: radial socket on a body that is not radial")
+            }
+            Self::TooFewFolds { path, fold_count } => write!(
+                f,
+                "{path}: radial body has {} folds; it needs at least 2",
+                fold_count.value()
+            ),
+            Self::BranchingWithoutSegments { path } => {
+                write!(f, "{path}: limb can branch but has no segments")
+            }
+            Self::MismatchedCurvePoints { path, min, max } => write!(
+                f,
+                "{path}: bounds have {min} and {max} points; they must match"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpeciesValidationError {}
+
+impl fmt::Display for SpeciesValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_all(f, "invalid species", &self.0)
+    }
+}
+
+impl std::error::Error for SpeciesValidationErrors {}
+
+impl fmt::Display for OrganismValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VertebraIndexOutOfBounds {
+                path,
+                index,
+                vertebra_count,
+            } => write!(
+                f,
+                "{path}: attached to vertebra {} of {vertebra_count}",
+                index.0
+            ),
+            Self::VertebraCountOutOfRange { count, min, max } => write!(
+                f,
+                "{count} vertebrae, but the species allows {} to {}",
+                min.value(),
+                max.value()
+            ),
+            Self::SymmetryMismatch { expected, found } => write!(
+                f,
+                "body symmetry is {found:?}, but the species is {expected:?}"
+            ),
+            Self::UnexpectedSocket { path } => {
+                write!(
+                    f,
+                    "{path}: no socket rule of the species allows this socket"
+                )
+            }
+            Self::PlacementMismatch { path } => {
+                write!(
+                    f,
+                    "{path}: medial, lateral or radial placement differs from the rule"
+                )
+            }
+            Self::FoldCountMismatch {
+                path,
+                expected,
+                found,
+            } => write!(f, "{path}: {found} folds, but the body has {expected}"),
+            Self::RequiredSocketEmpty { path } => write!(f, "{path}: required socket is empty"),
+            Self::AppendageClassMismatch { path, found } => {
+                write!(f, "{path}: {found:?} is not allowed on this socket")
+            }
+            Self::NotAllowed { path } => write!(f, "{path}: not in the species' allowed list"),
+            Self::OutOfRange { path } => write!(f, "{path}: outside the species' range"),
+        }
+    }
+}
+
+impl std::error::Error for OrganismValidationError {}
+
+impl fmt::Display for OrganismValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_all(f, "invalid organism", &self.0)
+    }
+}
+
+impl std::error::Error for OrganismValidationErrors {}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSeed => write!(f, "invalid genome seed"),
+            Self::ConstraintUnsatisfiable {
+                rule,
+                constraint,
+                attempts,
+            } => write!(
+                f,
+                "{rule}: could not satisfy {constraint} after {attempts} attempt{}",
+                if *attempts == 1 { "" } else { "s" }
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonEmptySpine => write!(f, "at least one vertebra"),
+            Self::SocketWithinSpine { vertebra_index } => {
+                write!(f, "a required socket on vertebra {}", vertebra_index.0)
+            }
+            Self::MatchingCurvePoints { min, max } => {
+                write!(f, "matching curve bounds ({min} and {max} points)")
+            }
+        }
+    }
+}
+
+impl fmt::Display for PhylogenyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateSpecies { name } => write!(f, "species {name:?} is already recorded"),
+            Self::UnknownParent { name } => {
+                write!(f, "parent species {name:?} has not been recorded")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PhylogenyError {}
+
+impl fmt::Display for PopulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyPopulation => write!(f, "population size must be above zero"),
+            Self::Generation(err) => write!(f, "could not generate an organism: {err}"),
+            Self::Organism(err) => write!(f, "bred an {err}"),
+            Self::Species(err) => write!(f, "diverged an {err}"),
+            Self::Phylogeny(err) => write!(f, "could not record a species: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PopulationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EmptyPopulation => None,
+            Self::Generation(err) => Some(err),
+            Self::Organism(err) => Some(err),
+            Self::Species(err) => Some(err),
+            Self::Phylogeny(err) => Some(err),
+        }
+    }
+}
+
+/// `"{what}: first; second"`, counting the problems if there are several
+fn write_all<E: fmt::Display>(f: &mut fmt::Formatter<'_>, what: &str, errors: &[E]) -> fmt::Result {
+    match errors.len() {
+        1 => write!(f, "{what}: ")?,
+        count => write!(f, "{what} ({count} problems): ")?,
+    }
+    for (i, err) in errors.iter().enumerate() {
+        if i > 0 {
+            write!(f, "; ")?;
+        }
+        write!(f, "{err}")?;
+    }
+    Ok(())
 }
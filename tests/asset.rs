@@ -0,0 +1,118 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_speciation::{
+    Species,
+    asset::{GeneratedOrganism, SpawnOrganism, SpeciesAssetPlugin},
+    primitives::*,
+};
+
+fn app(asset_dir: &str) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: asset_dir.to_string(),
+            ..default()
+        },
+        SpeciesAssetPlugin,
+    ));
+    app
+}
+
+fn vertebrae(app: &App, entity: Entity) -> Option<usize> {
+    app.world()
+        .get::<GeneratedOrganism>(entity)
+        .map(|organism| organism.torso().spine.vertebrae.len())
+}
+
+/// Update until `done` or a couple of seconds have passed, for loads that
+/// finish on other threads
+fn update_until(app: &mut App, done: impl Fn(&App) -> bool) {
+    for _ in 0..400 {
+        app.update();
+        if done(app) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn loads_species_files_and_reports_invalid_ones() {
+    use bevy::asset::LoadState;
+
+    let dir = std::env::temp_dir().join(format!("bevy-speciation-assets-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let quadruped = ron::to_string(&common::quadruped_genes()).unwrap();
+    std::fs::write(dir.join("quadruped.species.ron"), &quadruped).unwrap();
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.vertebra_count = InclusiveRange::new(Count::new(0), Count::new(0));
+    std::fs::write(
+        dir.join("broken.species.ron"),
+        ron::to_string(&genes).unwrap(),
+    )
+    .unwrap();
+
+    let mut app = app(dir.to_str().unwrap());
+    let server = app.world().resource::<AssetServer>().clone();
+    let species: Handle<Species> = server.load("quadruped.species.ron");
+    let broken: Handle<Species> = server.load("broken.species.ron");
+    let organism = app
+        .world_mut()
+        .spawn(SpawnOrganism {
+            species,
+            seed: GenomeSeed(3),
+        })
+        .id();
+
+    update_until(&mut app, |app| {
+        vertebrae(app, organism).is_some() && !server.load_state(&broken).is_loading()
+    });
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let expected = common::quadruped().generate(GenomeSeed(3)).unwrap();
+    assert_eq!(
+        vertebrae(&app, organism),
+        Some(expected.torso().spine.vertebrae.len())
+    );
+    assert!(matches!(server.load_state(&broken), LoadState::Failed(_)));
+}
+
+#[test]
+fn modified_species_regenerate_their_organisms() {
+    let mut app = app("assets");
+    let species = app
+        .world_mut()
+        .resource_mut::<Assets<Species>>()
+        .add(common::quadruped());
+    let organisms: Vec<Entity> = (0..3)
+        .map(|seed| {
+            app.world_mut()
+                .spawn(SpawnOrganism {
+                    species: species.clone(),
+                    seed: GenomeSeed(seed),
+                })
+                .id()
+        })
+        .collect();
+    app.update();
+    assert!(organisms.iter().all(|&e| vertebrae(&app, e).unwrap() <= 8));
+
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.vertebra_count = InclusiveRange::new(Count::new(12), Count::new(12));
+    *app.world_mut()
+        .resource_mut::<Assets<Species>>()
+        .get_mut(&species)
+        .unwrap() = Species::new(genes).unwrap();
+    update_until(&mut app, |app| {
+        organisms.iter().all(|&e| vertebrae(app, e) == Some(12))
+    });
+
+    assert!(organisms.iter().all(|&e| vertebrae(&app, e) == Some(12)));
+    for (seed, &entity) in organisms.iter().enumerate() {
+        let organism = app.world().get::<GeneratedOrganism>(entity).unwrap();
+        assert_eq!(organism.genome_seed(), GenomeSeed(seed as u64));
+    }
+}
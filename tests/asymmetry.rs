@@ -0,0 +1,103 @@
+mod common;
+
+use bevy_speciation::{
+    Organism, Species, UnvalidatedSpecies,
+    appendage::{Appendage, AppendageClass},
+    mutation::MutationRates,
+    primitives::*,
+    skeleton::{BoneClass, Side, SkeletonGenerator},
+    sockets_symmetry::{BodySymmetry, SymmetricSocket},
+    species::SideGenes,
+};
+
+/// The quadruped with a right forelimb far longer than the left
+fn fiddler_crab_genes() -> UnvalidatedSpecies {
+    let mut genes = common::quadruped_genes();
+    genes.symmetry = BodySymmetry::Asymmetric;
+    let mut claw = common::leg(AppendageClass::Forelimb);
+    claw.limb.segment = common::bone(1.0, 1.5);
+    genes.torso.spine.appendage_sockets[0].sides = SideGenes::Independent {
+        right: Box::new(WeightedChoice::new(claw, 1.0)),
+    };
+    genes
+}
+
+fn forelimbs(organism: &Organism) -> (&Appendage, &Appendage) {
+    let SymmetricSocket::Lateral(pair) = &organism.torso().spine.appendages[0].socket else {
+        panic!("expected a lateral pair");
+    };
+    (
+        pair.left.attachment.as_ref().unwrap(),
+        pair.right.attachment.as_ref().unwrap(),
+    )
+}
+
+fn longest_segment(appendage: &Appendage) -> f32 {
+    appendage
+        .structure
+        .segments
+        .iter()
+        .map(|segment| segment.bone.length.value())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn each_side_samples_its_own_genes() {
+    let species = Species::new(fiddler_crab_genes()).unwrap();
+    for seed in 0..10 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+        let (left, right) = forelimbs(&organism);
+        assert!(longest_segment(left) <= 0.5);
+        assert!(longest_segment(right) >= 1.0);
+
+        let mutant = species
+            .mutate(&organism, &MutationRates::default(), 0)
+            .unwrap();
+        assert!(longest_segment(forelimbs(&mutant).1) >= 1.0);
+        assert!(Organism::validate(organism.into(), &species).is_ok());
+    }
+}
+
+#[test]
+fn varied_sides_differ_but_stay_within_the_genes() {
+    let mut genes = common::quadruped_genes();
+    genes.symmetry = BodySymmetry::Asymmetric;
+    genes.torso.spine.appendage_sockets[1].sides = SideGenes::Varied {
+        variation: common::normalized(0.5),
+    };
+    let species = Species::new(genes).unwrap();
+
+    let organism = species.generate(GenomeSeed(5)).unwrap();
+    let SymmetricSocket::Lateral(pair) = &organism.torso().spine.appendages[1].socket else {
+        panic!("expected a lateral pair");
+    };
+    let (left, right) = (
+        pair.left.attachment.as_ref(),
+        pair.right.attachment.as_ref(),
+    );
+    assert_ne!(format!("{left:?}"), format!("{right:?}"));
+    assert_eq!(
+        left.unwrap().structure.segments.len(),
+        right.unwrap().structure.segments.len()
+    );
+    assert!(Organism::validate(organism.into(), &species).is_ok());
+}
+
+#[test]
+fn skeleton_names_follow_each_side() {
+    let species = Species::new(fiddler_crab_genes()).unwrap();
+    let organism = species.generate(GenomeSeed(2)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+
+    let (_, right) = forelimbs(&organism);
+    let claw = skeleton
+        .root
+        .iter_depth_first()
+        .filter(|node| node.id.class == BoneClass::Limb(AppendageClass::Forelimb))
+        .filter(|node| node.id.side == Some(Side::Right))
+        .find(|node| node.id.index == 0)
+        .unwrap();
+    assert_eq!(claw.id.name(), "forelimb_0_R_v0_s0");
+    assert_eq!(claw.length, right.structure.segments[0].bone.length);
+    assert!(claw.rest.translation.x < 0.0);
+}
@@ -0,0 +1,269 @@
+#![allow(dead_code)]
+
+use bevy::prelude::{Dir3, LinearRgba};
+use bevy_speciation::{
+    Species, UnvalidatedSpecies,
+    anatomical_features::{AnatomicalFeature, Protrusion, SensoryOrgan, SensoryType, SpineRow},
+    appendage::{AppendageClass, DigitCount, Terminus},
+    primitives::*,
+    sockets_symmetry::BodySymmetry,
+    species::*,
+    surface::{Metallic, Roughness, SurfacePattern},
+    tissue_muscle::{MuscleIntensity, TissueEnvelope},
+};
+
+pub fn normalized(value: f32) -> Normalized {
+    Normalized::new(value).unwrap()
+}
+
+pub fn length(value: f32) -> Length {
+    Length::new(value).unwrap()
+}
+
+pub fn taper(base: f32) -> Curve {
+    Curve::new(vec![
+        CurvePoint {
+            t: normalized(0.0),
+            value: base,
+        },
+        CurvePoint {
+            t: normalized(1.0),
+            value: base * 0.5,
+        },
+    ])
+    .unwrap()
+}
+
+pub fn tissue() -> TissueEnvelopeGenes {
+    TissueEnvelopeGenes {
+        profile: CrossSectionProfile::Circular,
+        radius_range: ValueRange {
+            min: taper(0.05),
+            max: taper(0.1),
+        },
+        bulge_count: InclusiveRange::new(Count::new(0), Count::new(2)),
+        bulge_intensity: ValueRange {
+            min: MuscleIntensity::new(0.0).unwrap(),
+            max: MuscleIntensity::new(0.5).unwrap(),
+        },
+    }
+}
+
+pub fn bone(min: f32, max: f32) -> BoneGenes {
+    BoneGenes {
+        length: ValueRange {
+            min: length(min),
+            max: length(max),
+        },
+        tissue: tissue(),
+    }
+}
+
+pub fn integument() -> IntegumentGenes {
+    IntegumentGenes {
+        base_color: ValueRange {
+            min: LinearRgba::rgb(0.2, 0.1, 0.0),
+            max: LinearRgba::rgb(0.8, 0.6, 0.3),
+        },
+        allowed_patterns: WeightedChoice::from_vec(vec![
+            Weighted::new(SurfacePattern::Furred, 3.0),
+            Weighted::new(SurfacePattern::Scaled, 1.0),
+        ])
+        .unwrap(),
+        roughness: ValueRange {
+            min: Roughness(normalized(0.3)),
+            max: Roughness(normalized(0.9)),
+        },
+        metallic: ValueRange {
+            min: Metallic(normalized(0.0)),
+            max: Metallic(normalized(0.1)),
+        },
+    }
+}
+
+pub fn leg(class: AppendageClass) -> AppendageGenes {
+    AppendageGenes {
+        class,
+        limb: LimbGenes {
+            segment_count: InclusiveRange::new(Count::new(2), Count::new(3)),
+            segment: bone(0.3, 0.5),
+            allowed_termini: WeightedChoice::from_vec(vec![
+                Weighted::new(
+                    Terminus::Paw {
+                        digits: DigitCount::new(4).unwrap(),
+                    },
+                    0.7,
+                ),
+                Weighted::new(Terminus::Hoof, 0.3),
+            ])
+            .unwrap(),
+            branching_probability: normalized(0.0),
+        },
+        patagium_probability: normalized(0.0),
+        integument: integument(),
+    }
+}
+
+pub fn tail() -> AppendageGenes {
+    AppendageGenes {
+        class: AppendageClass::Tail,
+        limb: LimbGenes {
+            segment_count: InclusiveRange::new(Count::new(3), Count::new(6)),
+            segment: bone(0.1, 0.2),
+            allowed_termini: WeightedChoice::new(Terminus::Tapered, 1.0),
+            branching_probability: normalized(0.0),
+        },
+        patagium_probability: normalized(0.0),
+        integument: integument(),
+    }
+}
+
+pub fn lateral(x: f32, y: f32, z: f32, normal: Dir3) -> SymmetricPlacement {
+    SymmetricPlacement::Lateral {
+        offset: LocalPosition::new(x, y, z),
+        normal,
+    }
+}
+
+pub fn medial(x: f32, y: f32, z: f32, normal: Dir3) -> SymmetricPlacement {
+    SymmetricPlacement::Medial(SocketPlacement {
+        position: LocalPosition::new(x, y, z),
+        normal,
+    })
+}
+
+pub fn radial(x: f32, y: f32, z: f32, normal: Dir3) -> SymmetricPlacement {
+    SymmetricPlacement::Radial {
+        offset: LocalPosition::new(x, y, z),
+        normal,
+    }
+}
+
+/// A four-legged, tailed creature with eyes, horns and dorsal spines
+pub fn quadruped_genes() -> UnvalidatedSpecies {
+    UnvalidatedSpecies {
+        name: "Quadruped".to_string(),
+        parent: None,
+        symmetry: BodySymmetry::Bilateral,
+        head: CraniumGenes {
+            bone: bone(0.3, 0.4),
+            sensory_sockets: vec![SensorySocketRule {
+                placement: lateral(0.1, 0.1, 0.15, Dir3::X),
+                allowed: WeightedChoice::new(
+                    SensoryOrgan {
+                        kind: SensoryType::Ocular,
+                        size: length(0.04),
+                    },
+                    1.0,
+                ),
+                required: true,
+            }],
+            mandible: None,
+            feature_sockets: vec![FeatureSocketRule {
+                vertebra_indices: NonEmpty::new(VertebraIndex(0)),
+                placement: lateral(0.1, 0.2, 0.0, Dir3::Y),
+                allowed: WeightedChoice::new(
+                    AnatomicalFeature::Horn(Protrusion {
+                        length: length(0.3),
+                        curvature: taper(0.2),
+                        tissue: TissueEnvelope {
+                            profile: CrossSectionProfile::Circular,
+                            radius_curve: taper(0.05),
+                            musculature: Vec::new(),
+                        },
+                    }),
+                    1.0,
+                ),
+                required: false,
+            }],
+            integument: integument(),
+        },
+        torso: TorsoGenes {
+            spine: SpineGenes {
+                vertebra_count: InclusiveRange::new(Count::new(4), Count::new(8)),
+                vertebra: bone(0.2, 0.3),
+                appendage_sockets: vec![
+                    AppendageSocketRule {
+                        vertebra_indices: NonEmpty::new(VertebraIndex(0)),
+                        placement: lateral(0.3, -0.1, 0.0, Dir3::NEG_Y),
+                        allowed: WeightedChoice::new(leg(AppendageClass::Forelimb), 1.0),
+                        required: true,
+                        sides: SideGenes::Mirrored,
+                    },
+                    AppendageSocketRule {
+                        vertebra_indices: NonEmpty::new(VertebraIndex(3)),
+                        placement: lateral(0.3, -0.1, 0.0, Dir3::NEG_Y),
+                        allowed: WeightedChoice::new(leg(AppendageClass::Hindlimb), 1.0),
+                        required: true,
+                        sides: SideGenes::Mirrored,
+                    },
+                    AppendageSocketRule {
+                        vertebra_indices: NonEmpty::new(VertebraIndex(3)),
+                        placement: medial(0.0, 0.0, -0.2, Dir3::NEG_Z),
+                        allowed: WeightedChoice::new(tail(), 1.0),
+                        required: false,
+                        sides: SideGenes::Mirrored,
+                    },
+                ],
+                feature_sockets: vec![FeatureSocketRule {
+                    vertebra_indices: NonEmpty::from_vec(vec![VertebraIndex(1), VertebraIndex(2)])
+                        .unwrap(),
+                    placement: medial(0.0, 0.2, 0.0, Dir3::Y),
+                    allowed: WeightedChoice::new(
+                        AnatomicalFeature::Spines(SpineRow {
+                            count: Count::new(3),
+                            spine_length: length(0.1),
+                            spacing: length(0.05),
+                        }),
+                        1.0,
+                    ),
+                    required: false,
+                }],
+            },
+            base_tissue: tissue(),
+            integument: integument(),
+        },
+    }
+}
+
+pub fn quadruped() -> Species {
+    Species::new(quadruped_genes()).unwrap()
+}
+
+/// A five-armed creature with one arm per fold around a short spine
+pub fn starfish_genes() -> UnvalidatedSpecies {
+    UnvalidatedSpecies {
+        name: "Starfish".to_string(),
+        parent: None,
+        symmetry: BodySymmetry::Radial {
+            fold_count: Count::new(5),
+        },
+        head: CraniumGenes {
+            bone: bone(0.1, 0.2),
+            sensory_sockets: Vec::new(),
+            mandible: None,
+            feature_sockets: Vec::new(),
+            integument: integument(),
+        },
+        torso: TorsoGenes {
+            spine: SpineGenes {
+                vertebra_count: InclusiveRange::new(Count::new(1), Count::new(2)),
+                vertebra: bone(0.1, 0.2),
+                appendage_sockets: vec![AppendageSocketRule {
+                    vertebra_indices: NonEmpty::new(VertebraIndex(0)),
+                    placement: radial(0.2, 0.0, 0.0, Dir3::X),
+                    allowed: WeightedChoice::new(leg(AppendageClass::Tentacle), 1.0),
+                    required: true,
+                    sides: SideGenes::Mirrored,
+                }],
+                feature_sockets: Vec::new(),
+            },
+            base_tissue: tissue(),
+            integument: integument(),
+        },
+    }
+}
+
+pub fn starfish() -> Species {
+    Species::new(starfish_genes()).unwrap()
+}
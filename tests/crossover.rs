@@ -0,0 +1,98 @@
+mod common;
+
+use bevy_speciation::{Organism, Species, crossover::CrossoverPolicy, primitives::GenomeSeed};
+
+const POLICIES: [CrossoverPolicy; 3] = [
+    CrossoverPolicy::Uniform,
+    CrossoverPolicy::PerRegion,
+    CrossoverPolicy::Blend,
+];
+
+fn parents(species: &Species, seed: u64) -> (Organism, Organism) {
+    (
+        species.generate(GenomeSeed(seed)).unwrap(),
+        species.generate(GenomeSeed(seed + 1000)).unwrap(),
+    )
+}
+
+fn vertebra_lengths(organism: &Organism) -> Vec<f32> {
+    organism
+        .torso()
+        .spine
+        .vertebrae
+        .iter()
+        .map(|vertebra| vertebra.bone.length.value())
+        .collect()
+}
+
+#[test]
+fn children_are_deterministic_and_siblings_differ() {
+    let species = common::quadruped();
+    let (a, b) = parents(&species, 1);
+
+    for policy in POLICIES {
+        let child = species.crossover(&a, &b, policy, 0).unwrap();
+        let again = species.crossover(&a, &b, policy, 0).unwrap();
+        let sibling = species.crossover(&a, &b, policy, 1).unwrap();
+
+        assert_eq!(child.genome_seed(), again.genome_seed());
+        assert_eq!(format!("{child:?}"), format!("{again:?}"));
+        assert_ne!(child.genome_seed(), sibling.genome_seed());
+    }
+}
+
+#[test]
+fn uniform_children_inherit_each_vertebra_from_a_parent() {
+    let species = common::quadruped();
+    for seed in 0..20 {
+        let (a, b) = parents(&species, seed);
+        let child = species
+            .crossover(&a, &b, CrossoverPolicy::Uniform, 0)
+            .unwrap();
+
+        let (from_a, from_b) = (vertebra_lengths(&a), vertebra_lengths(&b));
+        let lengths = vertebra_lengths(&child);
+        assert!(lengths.len() == from_a.len() || lengths.len() == from_b.len());
+        for (i, length) in lengths.iter().enumerate() {
+            assert!(from_a.get(i) == Some(length) || from_b.get(i) == Some(length));
+        }
+    }
+}
+
+#[test]
+fn blended_lengths_lie_between_the_parents() {
+    let species = common::quadruped();
+    for seed in 0..20 {
+        let (a, b) = parents(&species, seed);
+        let child = species
+            .crossover(&a, &b, CrossoverPolicy::Blend, 0)
+            .unwrap();
+
+        let (from_a, from_b) = (vertebra_lengths(&a), vertebra_lengths(&b));
+        for (i, length) in vertebra_lengths(&child).iter().enumerate() {
+            if let (Some(a), Some(b)) = (from_a.get(i), from_b.get(i)) {
+                assert!((a.min(*b) - 1e-6..=a.max(*b) + 1e-6).contains(length));
+            }
+        }
+    }
+}
+
+#[test]
+fn per_region_children_take_the_whole_head_from_one_parent() {
+    let species = common::quadruped();
+    for seed in 0..20 {
+        let (a, b) = parents(&species, seed);
+        let child = species
+            .crossover(&a, &b, CrossoverPolicy::PerRegion, 0)
+            .unwrap();
+
+        let head = child.head();
+        let parent = if head.bone.length == a.head().bone.length {
+            a.head()
+        } else {
+            b.head()
+        };
+        assert_eq!(head.bone.length, parent.bone.length);
+        assert_eq!(head.integument, parent.integument);
+    }
+}
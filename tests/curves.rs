@@ -0,0 +1,88 @@
+use bevy_speciation::primitives::{Curve, CurvePoint, Interpolation, Normalized};
+
+fn curve(points: &[(f32, f32)]) -> Option<Curve> {
+    Curve::new(
+        points
+            .iter()
+            .map(|&(t, value)| CurvePoint {
+                t: Normalized::new(t).unwrap(),
+                value,
+            })
+            .collect(),
+    )
+}
+
+fn zigzag() -> Curve {
+    curve(&[(0.0, 0.0), (0.25, 1.0), (0.5, 1.0), (1.0, 0.0)]).unwrap()
+}
+
+#[test]
+fn rejects_unsorted_or_repeated_t() {
+    assert!(curve(&[(0.5, 1.0), (0.2, 0.0)]).is_none());
+    assert!(curve(&[(0.2, 1.0), (0.2, 0.0)]).is_none());
+    assert!(curve(&[]).is_some());
+    assert!(curve(&[(0.2, 1.0), (0.6, 0.0)]).is_some());
+}
+
+#[test]
+fn clamps_outside_the_end_points() {
+    let curve = curve(&[(0.2, 1.0), (0.8, 3.0)]).unwrap();
+    assert_eq!(curve.evaluate(0.0), 1.0);
+    assert_eq!(curve.evaluate(1.0), 3.0);
+    assert_eq!(Curve::constant(0.4).evaluate(0.7), 0.4);
+    assert_eq!(self::curve(&[]).unwrap().evaluate(0.5), 0.0);
+}
+
+#[test]
+fn step_and_linear_between_points() {
+    let linear = curve(&[(0.0, 0.0), (0.5, 1.0), (1.0, 3.0)]).unwrap();
+    assert!((linear.evaluate(0.25) - 0.5).abs() < 1e-6);
+    assert!((linear.evaluate(0.75) - 2.0).abs() < 1e-6);
+
+    let step = linear.with_interpolation(Interpolation::Step);
+    assert_eq!(step.evaluate(0.25), 0.0);
+    assert_eq!(step.evaluate(0.5), 1.0);
+    assert_eq!(step.evaluate(0.99), 1.0);
+}
+
+#[test]
+fn cubic_modes_pass_through_every_point() {
+    for interpolation in [Interpolation::CatmullRom, Interpolation::MonotoneCubic] {
+        let curve = zigzag().with_interpolation(interpolation);
+        for point in curve.points() {
+            assert!((curve.evaluate(point.t.value()) - point.value).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn monotone_cubic_does_not_overshoot() {
+    let catmull_rom = zigzag().with_interpolation(Interpolation::CatmullRom);
+    let monotone = zigzag().with_interpolation(Interpolation::MonotoneCubic);
+
+    let samples = (0..=100).map(|i| i as f32 / 100.0);
+    assert!(
+        samples
+            .clone()
+            .any(|t| catmull_rom.evaluate(t) > 1.0 + 1e-3)
+    );
+    assert!(
+        samples
+            .map(|t| monotone.evaluate(t))
+            .all(|v| (-1e-5..=1.0 + 1e-5).contains(&v))
+    );
+}
+
+#[test]
+fn resamples_to_evenly_spaced_points() {
+    let source = zigzag().with_interpolation(Interpolation::MonotoneCubic);
+    let resampled = source.resample(5);
+
+    assert_eq!(resampled.len(), 5);
+    assert_eq!(resampled.interpolation(), Interpolation::MonotoneCubic);
+    for (i, point) in resampled.points().iter().enumerate() {
+        let t = i as f32 / 4.0;
+        assert_eq!(point.t.value(), t);
+        assert!((point.value - source.evaluate(t)).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use bevy_speciation::{
+    distance::DistanceWeights,
+    primitives::{GenomeSeed, Normalized},
+    speciation::DivergenceConfig,
+};
+
+fn only_vertebra_count() -> DistanceWeights {
+    DistanceWeights {
+        vertebra_count: 1.0,
+        bone_length: 0.0,
+        appendage_class: 0.0,
+        terminus: 0.0,
+        integument: 0.0,
+    }
+}
+
+#[test]
+fn organism_distance_is_a_normalized_metric() {
+    let species = common::quadruped();
+    let weights = DistanceWeights::default();
+    let a = species.generate(GenomeSeed(1)).unwrap();
+    let b = species.generate(GenomeSeed(2)).unwrap();
+
+    assert_eq!(a.distance(&a, &weights), 0.0);
+    let distance = a.distance(&b, &weights);
+    assert!(distance > 0.0 && distance <= 1.0);
+    assert_eq!(distance, b.distance(&a, &weights));
+}
+
+#[test]
+fn weights_select_components() {
+    let species = common::quadruped();
+    let organisms: Vec<_> = (0..20)
+        .map(|seed| species.generate(GenomeSeed(seed)).unwrap())
+        .collect();
+    let a = &organisms[0];
+    let b = organisms
+        .iter()
+        .find(|b| b.torso().spine.vertebrae.len() != a.torso().spine.vertebrae.len())
+        .unwrap();
+
+    let (count_a, count_b) = (
+        a.torso().spine.vertebrae.len() as f32,
+        b.torso().spine.vertebrae.len() as f32,
+    );
+    let expected = (count_a - count_b).abs() / count_a.max(count_b);
+    assert!((a.distance(b, &only_vertebra_count()) - expected).abs() < 1e-6);
+
+    let none = DistanceWeights {
+        vertebra_count: 0.0,
+        ..only_vertebra_count()
+    };
+    assert_eq!(a.distance(b, &none), 0.0);
+}
+
+#[test]
+fn species_distance_grows_with_divergence() {
+    let species = common::quadruped();
+    let weights = DistanceWeights::default();
+    assert_eq!(species.distance(&species, &weights), 0.0);
+
+    let one = Normalized::new(1.0).unwrap();
+    let daughter = species
+        .diverge(
+            &DivergenceConfig {
+                shift: one,
+                shift_step: Normalized::new(0.5).unwrap(),
+                ..Default::default()
+            },
+            GenomeSeed(7),
+        )
+        .unwrap();
+    let distance = species.distance(&daughter, &weights);
+    assert!(distance > 0.0 && distance <= 1.0);
+    assert_eq!(distance, daughter.distance(&species, &weights));
+}
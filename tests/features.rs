@@ -0,0 +1,251 @@
+mod common;
+
+use std::collections::HashSet;
+
+use bevy::prelude::{Dir3, Vec3};
+use bevy_speciation::{
+    Species,
+    anatomical_features::{AnatomicalFeature, FinStructure, MandibleStructure, SensoryType},
+    appendage::{AppendageClass, DigitCount, Terminus},
+    primitives::*,
+    skeleton::{BoneClass, BoneId, Side, SkeletonConfig, SkeletonGenerator, SkeletonNode},
+};
+
+/// The quadruped with its horns and spine row always grown
+fn horned() -> Species {
+    let mut genes = common::quadruped_genes();
+    genes.head.feature_sockets[0].required = true;
+    genes.torso.spine.feature_sockets[0].required = true;
+    Species::new(genes).unwrap()
+}
+
+fn bones(root: &SkeletonNode, class: BoneClass) -> Vec<&SkeletonNode> {
+    root.iter_depth_first()
+        .filter(|node| node.id.class == class)
+        .collect()
+}
+
+#[test]
+fn sensory_organs_get_attach_points_on_the_head() {
+    let organism = common::quadruped().generate(GenomeSeed(0)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+
+    let head = skeleton.root.find(&BoneId::head()).unwrap();
+    let eye = |side| {
+        head.children
+            .iter()
+            .find(|node| {
+                node.id == BoneId::feature(BoneClass::Sensory(SensoryType::Ocular), Some(side), 0)
+            })
+            .unwrap()
+    };
+    let (left, right) = (eye(Side::Left), eye(Side::Right));
+    assert_eq!(left.id.name(), "eye_0_L");
+    assert_eq!(left.length.value(), 0.04);
+    assert_eq!(left.rest.translation, Vec3::new(0.1, 0.1, 0.15));
+    assert!((left.rest.rotation * left.axis).abs_diff_eq(Vec3::X, 1e-6));
+    assert_eq!(right.rest.translation, Vec3::new(-0.1, 0.1, 0.15));
+    assert!((right.rest.rotation * right.axis).abs_diff_eq(Vec3::NEG_X, 1e-6));
+}
+
+#[test]
+fn horns_are_chains_that_follow_their_curvature() {
+    let organism = horned().generate(GenomeSeed(1)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+
+    let base = skeleton
+        .root
+        .find(&BoneId::feature(BoneClass::Horn, Some(Side::Left), 0).with_socket(0))
+        .unwrap();
+    assert!((base.rest.rotation * base.axis).abs_diff_eq(Vec3::Y, 1e-6));
+    let chain: Vec<&SkeletonNode> =
+        std::iter::successors(Some(base), |node| node.children.first()).collect();
+    assert_eq!(chain.len(), 4);
+
+    // Curvature runs from 0.2 to 0.1 radians per unit along a 0.3 long horn
+    for (i, segment) in chain.iter().enumerate().skip(1) {
+        assert_eq!(segment.id.index, i as u8);
+        assert!((segment.length.value() - 0.075).abs() < 1e-6);
+        assert!(segment.rest.translation.abs_diff_eq(Vec3::Y * 0.075, 1e-6));
+        let bend = 0.2 - 0.1 * i as f32 / 4.0;
+        assert!((segment.rest.rotation.to_axis_angle().1 - bend * 0.075).abs() < 1e-5);
+    }
+    assert!(chain[0].tissue.is_some());
+}
+
+#[test]
+fn spine_rows_and_fins_get_one_bone_per_spike_or_ray() {
+    let organism = horned().generate(GenomeSeed(2)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let spikes = bones(&skeleton.root, BoneClass::Spike);
+    assert_eq!(spikes.len(), 6);
+    assert!(
+        spikes
+            .iter()
+            .all(|spike| spike.length.value() == 0.1 && spike.id.side.is_none())
+    );
+    let vertebra = skeleton.root.find(&BoneId::spine(1)).unwrap();
+    let row: Vec<_> = vertebra
+        .children
+        .iter()
+        .filter(|node| node.id.class == BoneClass::Spike)
+        .collect();
+    assert_eq!(row.len(), 3);
+    assert!(
+        (row[1].rest.translation - row[0].rest.translation).abs_diff_eq(Vec3::NEG_Z * 0.05, 1e-6)
+    );
+
+    let mut genes = common::quadruped_genes();
+    let rule = &mut genes.torso.spine.feature_sockets[0];
+    rule.required = true;
+    rule.allowed = WeightedChoice::new(
+        AnatomicalFeature::Fin(FinStructure {
+            height: common::length(0.15),
+            length: common::length(0.2),
+            membrane_thickness: common::length(0.01),
+        }),
+        1.0,
+    );
+    let organism = Species::new(genes)
+        .unwrap()
+        .generate(GenomeSeed(2))
+        .unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let rays = bones(&skeleton.root, BoneClass::FinRay);
+    assert_eq!(rays.len(), 10);
+    assert_eq!(rays[0].id.name(), "fin_ray_0_v1_s0");
+    assert_eq!(rays[5].id.name(), "fin_ray_0_v2_s1");
+    assert!(rays.iter().all(|ray| ray.length.value() == 0.15));
+    let span = rays[4].rest.translation - rays[0].rest.translation;
+    assert!(span.abs_diff_eq(Vec3::NEG_Z * 0.2, 1e-6));
+    assert!((rays[0].rest.rotation * rays[0].axis).abs_diff_eq(*Dir3::Y, 1e-6));
+}
+
+#[test]
+fn feature_bones_can_be_turned_off() {
+    let organism = horned().generate(GenomeSeed(3)).unwrap();
+    let generator = SkeletonGenerator::new(SkeletonConfig {
+        feature_bones: false,
+        ..SkeletonConfig::default()
+    });
+    let skeleton = generator.generate(&organism);
+
+    assert!(skeleton.root.iter_depth_first().all(|node| !matches!(
+        node.id.class,
+        BoneClass::Horn | BoneClass::Spike | BoneClass::FinRay | BoneClass::Sensory(_)
+    )));
+    let with_features = SkeletonGenerator::with_default_config().generate(&organism);
+    assert!(
+        with_features.root.iter_depth_first().count() > skeleton.root.iter_depth_first().count()
+    );
+}
+
+#[test]
+fn fins_get_as_many_rays_as_configured() {
+    let mut genes = common::quadruped_genes();
+    let rule = &mut genes.torso.spine.feature_sockets[0];
+    rule.required = true;
+    rule.allowed = WeightedChoice::new(
+        AnatomicalFeature::Fin(FinStructure {
+            height: common::length(0.15),
+            length: common::length(0.2),
+            membrane_thickness: common::length(0.01),
+        }),
+        1.0,
+    );
+    let organism = Species::new(genes)
+        .unwrap()
+        .generate(GenomeSeed(2))
+        .unwrap();
+    for fin_rays in [0, 1, 3] {
+        let generator = SkeletonGenerator::new(SkeletonConfig {
+            fin_rays,
+            ..SkeletonConfig::default()
+        });
+        let skeleton = generator.generate(&organism);
+        let rays = bones(&skeleton.root, BoneClass::FinRay);
+        assert_eq!(rays.len(), 2 * fin_rays as usize);
+        assert!(rays.iter().all(|ray| ray.rest.translation.is_finite()));
+    }
+}
+
+/// Features of one kind on several sockets, next to a jaw of the head's own
+#[test]
+fn bone_ids_and_names_are_unique() {
+    let mut genes = common::quadruped_genes();
+    genes.head.feature_sockets[0].required = true;
+    let jaw = MandibleStructure {
+        segments: Vec::new(),
+    };
+    genes.head.mandible = Some(WeightedChoice::new(jaw.clone(), 1.0));
+    let horns = genes.head.feature_sockets[0].clone();
+    let eye = genes.head.sensory_sockets[0].allowed.iter().next().unwrap();
+    for feature in [
+        AnatomicalFeature::Mandible(jaw),
+        AnatomicalFeature::Sensory(eye.value.clone()),
+    ] {
+        let mut rule = horns.clone();
+        rule.allowed = WeightedChoice::new(feature, 1.0);
+        genes.head.feature_sockets.push(rule);
+    }
+    genes.head.feature_sockets.push(horns);
+    let spikes = genes.torso.spine.feature_sockets[0].clone();
+    genes.torso.spine.feature_sockets.push(spikes);
+    for rule in &mut genes.torso.spine.feature_sockets {
+        rule.required = true;
+    }
+    assert_unique_bones(&Species::new(genes).unwrap());
+}
+
+/// Branched, clawed limbs of two classes, plus a second forelimb rule, all
+/// on the first vertebra
+#[test]
+fn branched_limbs_keep_their_digits_apart() {
+    let mut genes = common::quadruped_genes();
+    let forelimbs = genes.torso.spine.appendage_sockets[0].clone();
+    let mut wings = forelimbs.clone();
+    wings.allowed = WeightedChoice::new(common::leg(AppendageClass::Wing), 1.0);
+    genes
+        .torso
+        .spine
+        .appendage_sockets
+        .extend([forelimbs, wings]);
+    for rule in &mut genes.torso.spine.appendage_sockets {
+        let mut limb = rule.allowed.iter().next().unwrap().value.clone();
+        limb.limb.branching_probability = common::normalized(1.0);
+        limb.limb.allowed_termini = WeightedChoice::new(
+            Terminus::Claw {
+                digits: DigitCount::new(3).unwrap(),
+            },
+            1.0,
+        );
+        rule.allowed = WeightedChoice::new(limb, 1.0);
+    }
+    let species = Species::new(genes).unwrap();
+    assert_unique_bones(&species);
+
+    let organism = species.generate(GenomeSeed(0)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let digit = skeleton
+        .root
+        .iter_depth_first()
+        .find(|node| {
+            node.id.class == BoneClass::Digit(AppendageClass::Wing)
+                && !node.id.branch_path.is_empty()
+        })
+        .unwrap();
+    assert!(digit.id.name().starts_with("wing_digit_"));
+    assert!(digit.id.name().contains("_b0_"));
+}
+
+fn assert_unique_bones(species: &Species) {
+    for seed in 0..10 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+        let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+        let nodes: Vec<_> = skeleton.root.iter_depth_first().collect();
+        let ids: HashSet<_> = nodes.iter().map(|node| &node.id).collect();
+        let names: HashSet<_> = nodes.iter().map(|node| node.id.name()).collect();
+        assert_eq!(ids.len(), nodes.len());
+        assert_eq!(names.len(), nodes.len());
+    }
+}
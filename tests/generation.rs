@@ -0,0 +1,286 @@
+mod common;
+
+use std::fmt::Write;
+
+use bevy_speciation::{
+    Organism, Species,
+    appendage::{AppendageClass, LimbStructure},
+    primitives::{Count, GenomeSeed, InclusiveRange, NonEmpty, VertebraIndex, WeightedChoice},
+    skeletal::Bone,
+    sockets_symmetry::{Socket, SymmetricSocket},
+    surface::Integument,
+    tissue_muscle::TissueEnvelope,
+};
+
+fn sockets<T>(socket: &SymmetricSocket<T>) -> Vec<&Socket<T>> {
+    match socket {
+        SymmetricSocket::Medial(socket) => vec![socket],
+        SymmetricSocket::Lateral(pair) => vec![&pair.left, &pair.right],
+        SymmetricSocket::Radial(folds) => folds.iter().collect(),
+    }
+}
+
+/// Compact, exact rendering of everything generation decides
+fn fingerprint(organism: &Organism) -> String {
+    fn bone(out: &mut String, bone: &Bone) {
+        write!(out, " {:?}", bone.length.value()).unwrap();
+        tissue(out, &bone.tissue);
+    }
+
+    fn tissue(out: &mut String, tissue: &TissueEnvelope) {
+        for point in tissue.radius_curve.points() {
+            write!(out, " r{:?}", point.value).unwrap();
+        }
+        for bulge in &tissue.musculature {
+            write!(
+                out,
+                " m{:?}@{:?}",
+                bulge.intensity.value(),
+                bulge.attachment.position.value()
+            )
+            .unwrap();
+        }
+    }
+
+    fn integument(out: &mut String, integument: &Integument) {
+        let color = integument.base_color;
+        write!(
+            out,
+            " {:?} {:?} {:?} {:?} {:?} {:?}",
+            integument.pattern,
+            color.red,
+            color.green,
+            color.blue,
+            integument.roughness.0.value(),
+            integument.metallic.0.value()
+        )
+        .unwrap();
+    }
+
+    fn limb(out: &mut String, limb: &LimbStructure) {
+        for segment in &limb.segments {
+            bone(out, &segment.bone);
+        }
+        write!(out, " {:?}", limb.terminus).unwrap();
+    }
+
+    let mut out = String::new();
+
+    out.push_str("head:");
+    bone(&mut out, &organism.head().bone);
+    integument(&mut out, &organism.head().integument);
+    for socket in organism.head().sensory_sockets.iter().flat_map(sockets) {
+        write!(out, " eye={}", socket.attachment.is_some()).unwrap();
+    }
+    for socket in organism.head().feature_sockets.iter().flat_map(sockets) {
+        write!(out, " horn={}", socket.attachment.is_some()).unwrap();
+    }
+    out.push('\n');
+
+    let torso = organism.torso();
+    for vertebra in torso.spine.vertebrae.iter() {
+        out.push_str("vertebra:");
+        bone(&mut out, &vertebra.bone);
+        out.push('\n');
+    }
+    for attachment in &torso.spine.appendages {
+        for socket in sockets(&attachment.socket) {
+            write!(out, "appendage {}:", attachment.vertebra_index.0).unwrap();
+            if let Some(appendage) = &socket.attachment {
+                write!(out, " {:?}", appendage.class).unwrap();
+                limb(&mut out, &appendage.structure);
+                integument(&mut out, &appendage.integument);
+            }
+            out.push('\n');
+        }
+    }
+    for attachment in &torso.spine.features {
+        for socket in sockets(&attachment.socket) {
+            writeln!(
+                out,
+                "feature {}: {}",
+                attachment.vertebra_index.0,
+                socket.attachment.is_some()
+            )
+            .unwrap();
+        }
+    }
+    out.push_str("torso:");
+    tissue(&mut out, &torso.base_tissue);
+    integument(&mut out, &torso.integument);
+    out.push('\n');
+
+    out
+}
+
+fn generate(seed: u64) -> String {
+    let organism = common::quadruped().generate(GenomeSeed(seed)).unwrap();
+    fingerprint(&organism)
+}
+
+#[test]
+fn generation_is_deterministic() {
+    let species = common::quadruped();
+    for seed in 0..32 {
+        let first = species.generate(GenomeSeed(seed)).unwrap();
+        let second = species.generate(GenomeSeed(seed)).unwrap();
+        assert_eq!(fingerprint(&first), fingerprint(&second));
+    }
+}
+
+#[test]
+fn generated_organisms_stay_within_their_genes() {
+    let species = common::quadruped();
+    for seed in 0..32 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+
+        let vertebrae = organism.torso().spine.vertebrae.len() as u8;
+        assert!((4..=8).contains(&vertebrae));
+        for vertebra in organism.torso().spine.vertebrae.iter() {
+            assert!((0.2..=0.3).contains(&vertebra.bone.length.value()));
+        }
+        assert!((0.3..=0.4).contains(&organism.head().bone.length.value()));
+
+        // Eyes, forelimbs and hindlimbs are required on both sides
+        for socket in organism.head().sensory_sockets.iter().flat_map(sockets) {
+            assert!(socket.attachment.is_some());
+        }
+        let classes: Vec<_> = organism
+            .torso()
+            .spine
+            .appendages
+            .iter()
+            .flat_map(|attachment| sockets(&attachment.socket))
+            .filter_map(|socket| socket.attachment.as_ref().map(|a| a.class))
+            .collect();
+        for class in [AppendageClass::Forelimb, AppendageClass::Hindlimb] {
+            assert_eq!(classes.iter().filter(|c| **c == class).count(), 2);
+        }
+
+        assert!(Organism::validate(organism.into(), &species).is_ok());
+    }
+}
+
+#[test]
+fn spines_are_drawn_long_enough_for_required_sockets() {
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.vertebra_count = InclusiveRange::new(Count::new(1), Count::new(8));
+    genes.torso.spine.appendage_sockets[1].vertebra_indices = NonEmpty::new(VertebraIndex(6));
+    let species = Species::new(genes).unwrap();
+
+    let mut counts = [0; 9];
+    for seed in 0..500 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+        counts[organism.torso().spine.vertebrae.len()] += 1;
+    }
+    assert_eq!(counts[..7], [0; 7]);
+    assert!(counts[7] > 0 && counts[8] > 0);
+}
+
+#[test]
+fn gene_changes_stay_local_to_their_part() {
+    let mut genes = common::quadruped_genes();
+    genes.head.bone = common::bone(0.5, 0.6);
+    genes.head.sensory_sockets.clear();
+    let headless = Species::new(genes).unwrap();
+
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.appendage_sockets[2].allowed = WeightedChoice::new(common::tail(), 3.0);
+    genes.torso.spine.appendage_sockets[2]
+        .allowed
+        .push(common::leg(AppendageClass::Hindlimb), 1.0);
+    let retailed = Species::new(genes).unwrap();
+
+    let species = common::quadruped();
+    for seed in 0..16 {
+        let original = species.generate(GenomeSeed(seed)).unwrap();
+        let head_changed = headless.generate(GenomeSeed(seed)).unwrap();
+        assert_eq!(
+            format!("{:?}", original.torso()),
+            format!("{:?}", head_changed.torso())
+        );
+
+        let tail_changed = retailed.generate(GenomeSeed(seed)).unwrap();
+        assert_eq!(
+            format!("{:?}", original.head()),
+            format!("{:?}", tail_changed.head())
+        );
+        let spine = |organism: &Organism| {
+            let spine = &organism.torso().spine;
+            format!("{:?} {:?}", spine.vertebrae, &spine.appendages[..2])
+        };
+        assert_eq!(spine(&original), spine(&tail_changed));
+    }
+}
+
+#[test]
+fn golden_seed_0() {
+    assert_eq!(generate(0), GOLDEN_0);
+}
+
+#[test]
+fn golden_seed_42() {
+    assert_eq!(generate(42), GOLDEN_42);
+}
+
+#[test]
+fn golden_seed_max() {
+    assert_eq!(generate(u64::MAX), GOLDEN_MAX);
+}
+
+const GOLDEN_0: &str = "\
+head: 0.32201234 r0.097078174 r0.025138995 m0.48320654@0.40594554 m0.34135947@0.10199052 Furred 0.3005053 0.18375441 0.050252654 0.48261434 0.08228939 eye=true eye=true horn=false horn=false
+vertebra: 0.24534391 r0.054834634 r0.04384397 m0.44306248@0.25710028 m0.1594564@0.31506932
+vertebra: 0.21952522 r0.06903656 r0.032155775
+vertebra: 0.2798569 r0.0787994 r0.03862211
+vertebra: 0.20382148 r0.08188541 r0.039620515 m0.45386073@0.41053468
+vertebra: 0.21211402 r0.07718308 r0.03820492 m0.0888412@0.20671648 m0.39026538@0.5754613
+vertebra: 0.20008717 r0.075969175 r0.029962052 m0.104413@0.095971465
+vertebra: 0.29346877 r0.08774467 r0.046167802 m0.36073694@0.17932355
+appendage 0: Forelimb 0.39321667 r0.0666999 r0.028706886 0.4208034 r0.08907134 r0.02670399 m0.07682747@0.1055969 m0.27266842@0.28962702 Paw { digits: DigitCount(4) } Furred 0.5797591 0.4164659 0.18987954 0.8410142 0.032533187
+appendage 0: Forelimb 0.39321667 r0.0666999 r0.028706886 0.4208034 r0.08907134 r0.02670399 m0.07682747@0.1055969 m0.27266842@0.28962702 Paw { digits: DigitCount(4) } Furred 0.5797591 0.4164659 0.18987954 0.8410142 0.032533187
+appendage 3: Hindlimb 0.38105687 r0.08219536 r0.04136277 m0.454543@0.384233 m0.23835057@0.31934386 0.4627701 r0.08243126 r0.02577994 0.3048738 r0.05844922 r0.02694759 Hoof Scaled 0.647377 0.47281417 0.2236885 0.44706148 0.04633251
+appendage 3: Hindlimb 0.38105687 r0.08219536 r0.04136277 m0.454543@0.384233 m0.23835057@0.31934386 0.4627701 r0.08243126 r0.02577994 0.3048738 r0.05844922 r0.02694759 Hoof Scaled 0.647377 0.47281417 0.2236885 0.44706148 0.04633251
+appendage 3:
+feature 1: false
+feature 2: true
+torso: r0.06190741 r0.033775963 Furred 0.5261431 0.3717859 0.16307154 0.31130263 0.06395922
+";
+const GOLDEN_42: &str = "\
+head: 0.37657958 r0.07825661 r0.036533188 m0.28754804@0.98976326 m0.4720884@0.44052994 Scaled 0.7802925 0.58357704 0.29014623 0.6935679 0.0018413902 eye=true eye=true horn=false horn=false
+vertebra: 0.26962876 r0.06107328 r0.04242751 m0.37432668@0.4047323
+vertebra: 0.23690577 r0.0794387 r0.02865112 m0.33734152@0.31609923 m0.0060910583@0.38300598
+vertebra: 0.2997037 r0.07126124 r0.040682517 m0.30832@0.8424842 m0.051026434@0.46396416
+vertebra: 0.24971566 r0.0813729 r0.04436568 m0.19189373@0.8559553
+vertebra: 0.29876977 r0.055885624 r0.03974068 m0.33820555@0.86801845 m0.1756441@0.8051688
+vertebra: 0.28730768 r0.08223957 r0.035862118 m0.22071585@0.95618564 m0.4542891@0.7162909
+vertebra: 0.22532827 r0.08440295 r0.048309334 m0.28801444@0.84269905 m0.25957164@0.6678836
+vertebra: 0.26293558 r0.07446847 r0.04968736
+appendage 0: Forelimb 0.40570924 r0.08895436 r0.03642977 m0.40136448@0.72368544 m0.41094896@0.11793536 0.38220146 r0.082784265 r0.048247896 0.3268419 r0.09702788 r0.03601508 m0.20006505@0.41540933 m0.16978809@0.52729464 Paw { digits: DigitCount(4) } Scaled 0.6617402 0.4847835 0.2308701 0.8242227 0.07230657
+appendage 0: Forelimb 0.40570924 r0.08895436 r0.03642977 m0.40136448@0.72368544 m0.41094896@0.11793536 0.38220146 r0.082784265 r0.048247896 0.3268419 r0.09702788 r0.03601508 m0.20006505@0.41540933 m0.16978809@0.52729464 Paw { digits: DigitCount(4) } Scaled 0.6617402 0.4847835 0.2308701 0.8242227 0.07230657
+appendage 3: Hindlimb 0.4767415 r0.09067988 r0.030787582 m0.13821733@0.3537116 0.4759537 r0.07135571 r0.035316803 m0.4388636@0.24863982 0.30780017 r0.06809738 r0.047932 Paw { digits: DigitCount(4) } Scaled 0.69277036 0.510642 0.2463852 0.8895667 0.06374773
+appendage 3: Hindlimb 0.4767415 r0.09067988 r0.030787582 m0.13821733@0.3537116 0.4759537 r0.07135571 r0.035316803 m0.4388636@0.24863982 0.30780017 r0.06809738 r0.047932 Paw { digits: DigitCount(4) } Scaled 0.69277036 0.510642 0.2463852 0.8895667 0.06374773
+appendage 3: Tail 0.19757214 r0.09105769 r0.035085537 0.19307083 r0.09196368 r0.042342424 m0.24144015@0.30790436 0.123048246 r0.05568795 r0.036186807 m0.04054469@0.07019955 0.18866321 r0.06209474 r0.028338801 m0.33953887@0.4167657 m0.22410211@0.08568221 Tapered Scaled 0.49986643 0.3498887 0.14993322 0.35570693 0.0012968838
+feature 1: true
+feature 2: false
+torso: r0.05229305 r0.027573757 Furred 0.4670844 0.32257035 0.13354221 0.6744971 0.041150082
+";
+const GOLDEN_MAX: &str = "\
+head: 0.3524578 r0.08431263 r0.0435844 Scaled 0.73035276 0.5419607 0.26517642 0.5102953 0.007276279 eye=true eye=true horn=true horn=true
+vertebra: 0.20462362 r0.052261658 r0.02988123 m0.42102218@0.20869082 m0.32467306@0.678839
+vertebra: 0.2526254 r0.06843176 r0.040691733 m0.28021115@0.92547286 m0.27345678@0.05866629
+vertebra: 0.20055257 r0.08570613 r0.043270238 m0.46540436@0.6460264
+vertebra: 0.29000586 r0.08556412 r0.030220078 m0.30882484@0.34892315 m0.14563248@0.4817328
+vertebra: 0.24745362 r0.06828307 r0.028573668
+vertebra: 0.2256794 r0.07242479 r0.044985477 m0.17358547@0.12699342
+vertebra: 0.21671483 r0.081681535 r0.03483885 m0.3753364@0.16526693 m0.42022282@0.22658044
+vertebra: 0.26357955 r0.09135689 r0.04951246 m0.12197527@0.4487449
+appendage 0: Forelimb 0.4617175 r0.07543297 r0.03693357 m0.4759584@0.066358924 0.3631279 r0.06699403 r0.02834194 m0.3764075@0.21928382 m0.10749912@0.9630977 Paw { digits: DigitCount(4) } Furred 0.7049043 0.5207536 0.25245216 0.70732164 0.04763757
+appendage 0: Forelimb 0.4617175 r0.07543297 r0.03693357 m0.4759584@0.066358924 0.3631279 r0.06699403 r0.02834194 m0.3764075@0.21928382 m0.10749912@0.9630977 Paw { digits: DigitCount(4) } Furred 0.7049043 0.5207536 0.25245216 0.70732164 0.04763757
+appendage 3: Hindlimb 0.4928681 r0.05428878 r0.047072053 m0.18989074@0.6750736 0.4714075 r0.08727962 r0.046841428 m0.27730867@0.68136424 0.30442473 r0.06345113 r0.028893419 Paw { digits: DigitCount(4) } Furred 0.651857 0.4765475 0.2259285 0.74968165 0.09973451
+appendage 3: Hindlimb 0.4928681 r0.05428878 r0.047072053 m0.18989074@0.6750736 0.4714075 r0.08727962 r0.046841428 m0.27730867@0.68136424 0.30442473 r0.06345113 r0.028893419 Paw { digits: DigitCount(4) } Furred 0.651857 0.4765475 0.2259285 0.74968165 0.09973451
+appendage 3: Tail 0.1193867 r0.08562441 r0.041740306 0.170648 r0.06728484 r0.030931376 m0.37247375@0.9846329 m0.3218434@0.6534479 0.15814485 r0.09442089 r0.046894453 0.13120414 r0.052097127 r0.039880943 Tapered Scaled 0.38694614 0.25578848 0.09347308 0.5481235 0.097596824
+feature 1: true
+feature 2: true
+torso: r0.068821885 r0.037960116 m0.11256519@0.5828044 m0.29382408@0.9032779 Furred 0.52902746 0.37418956 0.16451374 0.72627306 0.026219612
+";
@@ -0,0 +1,189 @@
+mod common;
+
+use bevy::{
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    prelude::{Dir3, Vec2, Vec3, Vec4},
+};
+use bevy_speciation::{
+    SkeletonGenerator,
+    mesh::{TissueMeshBuilder, TissueMeshConfig},
+    primitives::{AspectRatio, CrossSectionProfile, Curve, GenomeSeed, Radians},
+    tissue_muscle::{MuscleAttachment, MuscleBulge, MuscleIntensity, MuscleSpread, TissueEnvelope},
+};
+
+fn positions(mesh: &Mesh) -> Vec<Vec3> {
+    match mesh.attribute(Mesh::ATTRIBUTE_POSITION).unwrap() {
+        VertexAttributeValues::Float32x3(values) => values.iter().map(|v| Vec3::from(*v)).collect(),
+        _ => panic!("positions are not Float32x3"),
+    }
+}
+
+fn normals(mesh: &Mesh) -> Vec<Vec3> {
+    match mesh.attribute(Mesh::ATTRIBUTE_NORMAL).unwrap() {
+        VertexAttributeValues::Float32x3(values) => values.iter().map(|v| Vec3::from(*v)).collect(),
+        _ => panic!("normals are not Float32x3"),
+    }
+}
+
+fn indices(mesh: &Mesh) -> Vec<u32> {
+    match mesh.indices().unwrap() {
+        Indices::U32(values) => values.clone(),
+        Indices::U16(values) => values.iter().map(|&i| i as u32).collect(),
+    }
+}
+
+fn tube(profile: CrossSectionProfile) -> TissueEnvelope {
+    TissueEnvelope {
+        profile,
+        radius_curve: common::taper(0.1),
+        musculature: Vec::new(),
+    }
+}
+
+#[test]
+fn builds_a_mesh_for_every_bone_with_tissue() {
+    let organism = common::quadruped().generate(GenomeSeed(5)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let meshes = TissueMeshBuilder::with_default_config().build_skeleton(&skeleton);
+
+    let with_tissue = skeleton.iter().filter(|node| node.tissue.is_some()).count();
+    assert_eq!(meshes.len(), with_tissue);
+
+    for (_, mesh) in &meshes {
+        let vertex_count = mesh.count_vertices();
+        assert_eq!(positions(mesh).len(), vertex_count);
+        assert_eq!(normals(mesh).len(), vertex_count);
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_some());
+        assert!(indices(mesh).iter().all(|&i| (i as usize) < vertex_count));
+        assert!(
+            normals(mesh)
+                .iter()
+                .all(|n| (n.length() - 1.0).abs() < 1e-4)
+        );
+    }
+}
+
+#[test]
+fn triangles_face_outward() {
+    let mesh = TissueMeshBuilder::new(TissueMeshConfig {
+        caps: false,
+        ..Default::default()
+    })
+    .build(
+        &tube(CrossSectionProfile::Circular),
+        common::length(1.0),
+        Dir3::NEG_Z,
+    );
+    let positions = positions(&mesh);
+    let normals = normals(&mesh);
+
+    for triangle in indices(&mesh).chunks(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| triangle[i] as usize);
+        let face = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+        assert!(face.dot(normals[a] + normals[b] + normals[c]) > 0.0);
+    }
+}
+
+#[test]
+fn sweeps_along_the_axis_and_follows_the_radius_curve() {
+    let mesh = TissueMeshBuilder::new(TissueMeshConfig {
+        caps: false,
+        ..Default::default()
+    })
+    .build(
+        &tube(CrossSectionProfile::Circular),
+        common::length(2.0),
+        Dir3::X,
+    );
+
+    for position in positions(&mesh) {
+        let t = position.x / 2.0;
+        assert!((-1e-4..=1.0 + 1e-4).contains(&t));
+        let expected_radius = 0.1 - 0.05 * t;
+        assert!((Vec2::new(position.y, position.z).length() - expected_radius).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn elliptical_profile_stretches_one_side() {
+    let mesh = TissueMeshBuilder::with_default_config().build(
+        &tube(CrossSectionProfile::Elliptical(
+            AspectRatio::new(2.0).unwrap(),
+        )),
+        common::length(1.0),
+        Dir3::Z,
+    );
+    let base_ring: Vec<f32> = positions(&mesh)
+        .iter()
+        .filter(|p| p.z.abs() < 1e-4)
+        .map(|p| Vec2::new(p.x, p.y).length())
+        .filter(|distance| *distance > 1e-4)
+        .collect();
+    let widest = base_ring.iter().copied().fold(f32::MIN, f32::max);
+    let narrowest = base_ring.iter().copied().fold(f32::MAX, f32::min);
+    assert!((widest - 0.2).abs() < 1e-3);
+    assert!((narrowest - 0.1).abs() < 1e-3);
+}
+
+#[test]
+fn muscle_bulges_push_the_surface_out_near_their_attachment() {
+    let mut tissue = tube(CrossSectionProfile::Circular);
+    tissue.radius_curve = Curve::constant(0.1);
+    tissue.musculature.push(MuscleBulge {
+        attachment: MuscleAttachment {
+            position: common::normalized(0.5),
+            radial_angle: Radians::new(0.0),
+        },
+        intensity: MuscleIntensity::new(0.5).unwrap(),
+        spread: MuscleSpread::new(0.1).unwrap(),
+    });
+
+    let mesh = TissueMeshBuilder::new(TissueMeshConfig {
+        caps: false,
+        ..Default::default()
+    })
+    .build(&tissue, common::length(1.0), Dir3::Z);
+
+    let radii: Vec<f32> = positions(&mesh)
+        .iter()
+        .map(|p| Vec2::new(p.x, p.y).length())
+        .collect();
+    let largest = radii.iter().copied().fold(f32::MIN, f32::max);
+    let smallest = radii.iter().copied().fold(f32::MAX, f32::min);
+    assert!((largest - 0.15).abs() < 1e-3);
+    assert!((smallest - 0.1).abs() < 1e-3);
+}
+
+#[test]
+fn skinned_mesh_binds_vertices_to_their_bones() {
+    let organism = common::quadruped().generate(GenomeSeed(5)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let skinned = TissueMeshBuilder::with_default_config().build_skinned(&skeleton);
+    let bones: Vec<_> = skeleton.iter().collect();
+    assert_eq!(skinned.inverse_bindposes.len(), bones.len());
+
+    let Some(VertexAttributeValues::Uint16x4(joints)) =
+        skinned.mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX)
+    else {
+        panic!("joint indices are not Uint16x4");
+    };
+    let Some(VertexAttributeValues::Float32x4(weights)) =
+        skinned.mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT)
+    else {
+        panic!("joint weights are not Float32x4");
+    };
+    let positions = positions(&skinned.mesh);
+    assert_eq!(joints.len(), positions.len());
+    assert_eq!(weights.len(), positions.len());
+
+    for ((position, joint), weight) in positions.iter().zip(joints).zip(weights) {
+        assert!((Vec4::from(*weight).element_sum() - 1.0).abs() < 1e-5);
+        assert!(joint.iter().all(|&j| (j as usize) < bones.len()));
+
+        // Undoing the bind pose puts the vertex back along its own bone
+        let bone = bones[joint[0] as usize];
+        let local = skinned.inverse_bindposes[joint[0] as usize].transform_point3(*position);
+        let along = local.dot(bone.axis.normalize());
+        assert!((-1e-4..=bone.length.value() + 1e-4).contains(&along));
+    }
+}
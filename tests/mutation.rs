@@ -0,0 +1,170 @@
+mod common;
+
+use bevy_speciation::{
+    Species,
+    mutation::{MutationRates, SpeciesMutationRates},
+    primitives::{GenomeSeed, NonEmpty, Normalized, VertebraIndex},
+    sockets_symmetry::SymmetricSocket,
+};
+
+fn always() -> MutationRates {
+    let one = Normalized::new(1.0).unwrap();
+    MutationRates {
+        length: one,
+        color: one,
+        terminus: one,
+        vertebra_count: one,
+        socket_fill: one,
+        step: Normalized::new(0.5).unwrap(),
+    }
+}
+
+fn never() -> MutationRates {
+    let zero = Normalized::new(0.0).unwrap();
+    MutationRates {
+        length: zero,
+        color: zero,
+        terminus: zero,
+        vertebra_count: zero,
+        socket_fill: zero,
+        step: zero,
+    }
+}
+
+#[test]
+fn mutants_stay_within_the_species() {
+    let species = common::quadruped();
+    let genes = &species.torso().spine.vertebra.length;
+
+    for seed in 0..20 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+        let mutant = species.mutate(&organism, &always(), 0).unwrap();
+
+        let vertebrae = &mutant.torso().spine.vertebrae;
+        assert_ne!(vertebrae.len(), organism.torso().spine.vertebrae.len());
+        assert!(
+            (species.min_vertebrae().value() as usize..=species.max_vertebrae().value() as usize)
+                .contains(&vertebrae.len())
+        );
+        for vertebra in vertebrae.iter() {
+            assert!((genes.min..=genes.max).contains(&vertebra.bone.length));
+        }
+        assert!(
+            mutant
+                .torso()
+                .spine
+                .appendages
+                .iter()
+                .all(|attachment| (attachment.vertebra_index.0 as usize) < vertebrae.len())
+        );
+    }
+}
+
+#[test]
+fn mutation_is_deterministic_and_rates_of_zero_change_nothing() {
+    let species = common::quadruped();
+    let organism = species.generate(GenomeSeed(9)).unwrap();
+
+    let mutant = species.mutate(&organism, &always(), 3).unwrap();
+    let again = species.mutate(&organism, &always(), 3).unwrap();
+    assert_eq!(format!("{mutant:?}"), format!("{again:?}"));
+
+    let unchanged = species.mutate(&organism, &never(), 0).unwrap();
+    assert_ne!(unchanged.genome_seed(), organism.genome_seed());
+    assert_eq!(
+        format!("{:?}", unchanged.torso()),
+        format!("{:?}", organism.torso())
+    );
+    assert_eq!(
+        format!("{:?}", unchanged.head()),
+        format!("{:?}", organism.head())
+    );
+}
+
+#[test]
+fn lateral_pairs_mutate_together() {
+    let species = common::quadruped();
+    for seed in 0..10 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+        let mutant = species.mutate(&organism, &always(), 0).unwrap();
+
+        for attachment in &mutant.torso().spine.appendages {
+            if let SymmetricSocket::Lateral(pair) = &attachment.socket {
+                assert_eq!(
+                    format!("{:?}", pair.left.attachment),
+                    format!("{:?}", pair.right.attachment)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn species_mutation_changes_genes_and_still_validates() {
+    let one = Normalized::new(1.0).unwrap();
+    let rates = SpeciesMutationRates {
+        range: one,
+        allowed_appendage: one,
+        required: one,
+        step: Normalized::new(0.5).unwrap(),
+    };
+    let genes = common::quadruped_genes();
+    let mutated = genes.mutate(&rates, GenomeSeed(4));
+
+    let before = &genes.torso.spine.appendage_sockets;
+    let after = &mutated.torso.spine.appendage_sockets;
+    for (before, after) in before.iter().zip(after) {
+        assert_ne!(before.required, after.required);
+        assert!(after.allowed.len() > before.allowed.len());
+    }
+    assert_ne!(
+        genes.torso.spine.vertebra.length.min,
+        mutated.torso.spine.vertebra.length.min
+    );
+
+    assert!(Species::new(mutated).is_ok());
+}
+
+#[test]
+fn tissue_and_color_ranges_mutate_too() {
+    let rates = SpeciesMutationRates {
+        range: Normalized::new(1.0).unwrap(),
+        allowed_appendage: Normalized::new(0.0).unwrap(),
+        required: Normalized::new(0.0).unwrap(),
+        step: Normalized::new(0.5).unwrap(),
+    };
+    let genes = common::quadruped_genes();
+    let (mut color, mut radius, mut bulges) = (false, false, false);
+    for seed in 0..8 {
+        let mutated = genes.mutate(&rates, GenomeSeed(seed));
+        color |= mutated.head.integument.base_color != genes.head.integument.base_color;
+        let (before, after) = (&genes.head.bone.tissue, &mutated.head.bone.tissue);
+        radius |= after.radius_range != before.radius_range;
+        bulges |= after.bulge_intensity != before.bulge_intensity;
+        assert_eq!(
+            mutated.torso.spine.vertebra_count,
+            genes.torso.spine.vertebra_count
+        );
+        assert!(Species::new(mutated).is_ok());
+    }
+    assert!(color && radius && bulges);
+}
+
+#[test]
+fn vertebrae_holding_required_sockets_are_never_dropped() {
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.appendage_sockets[1].vertebra_indices = NonEmpty::new(VertebraIndex(5));
+    let species = Species::new(genes).unwrap();
+    let rates = MutationRates {
+        vertebra_count: Normalized::new(1.0).unwrap(),
+        ..never()
+    };
+
+    for seed in 0..8 {
+        let mut organism = species.generate(GenomeSeed(seed)).unwrap();
+        for index in 0..32 {
+            organism = species.mutate(&organism, &rates, index).unwrap();
+            assert!(organism.torso().spine.vertebrae.len() >= 6);
+        }
+    }
+}
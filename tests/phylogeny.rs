@@ -0,0 +1,103 @@
+mod common;
+
+use bevy_speciation::{
+    Species,
+    phylogeny::{GeneChange, Phylogeny},
+    primitives::{GenomeSeed, Normalized},
+    speciation::DivergenceConfig,
+    validation_errors::PhylogenyError,
+};
+
+fn daughter(parent: &Species, name: &str, seed: u64) -> Species {
+    parent
+        .diverge(
+            &DivergenceConfig {
+                shift: Normalized::new(1.0).unwrap(),
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            GenomeSeed(seed),
+        )
+        .unwrap()
+}
+
+#[test]
+fn answers_lineage_queries_and_exports_newick() {
+    let root = common::quadruped();
+    let a = daughter(&root, "Strider", 1);
+    let b = daughter(&root, "Burrower", 2);
+    let c = daughter(&a, "Long Strider", 3);
+
+    let mut tree = Phylogeny::new();
+    let root_id = tree.record(root, 0).unwrap();
+    let a_id = tree.record(a, 10).unwrap();
+    let b_id = tree.record(b, 15).unwrap();
+    let c_id = tree.record(c, 30).unwrap();
+
+    assert_eq!(tree.get(c_id).parent, Some(a_id));
+    assert!(!tree.get(a_id).changes.is_empty());
+    assert!(tree.get(root_id).changes.is_empty());
+
+    assert_eq!(tree.common_ancestor(b_id, c_id), Some(root_id));
+    assert_eq!(tree.common_ancestor(a_id, c_id), Some(a_id));
+    assert_eq!(tree.distance(b_id, c_id), Some(3));
+    assert_eq!(tree.distance(c_id, c_id), Some(0));
+    assert_eq!(tree.generations_apart(b_id, c_id), Some(45));
+    assert_eq!(tree.descendants(root_id), vec![a_id, c_id, b_id]);
+    assert!(tree.descendants(b_id).is_empty());
+
+    assert_eq!(
+        tree.to_newick(),
+        "(('Long Strider':20)Strider:10,Burrower:15)Quadruped;\n"
+    );
+}
+
+#[test]
+fn rejects_duplicates_and_orphans() {
+    let root = common::quadruped();
+    let a = daughter(&root, "Strider", 1);
+
+    let mut tree = Phylogeny::new();
+    assert_eq!(
+        tree.record(a.clone(), 5).unwrap_err(),
+        PhylogenyError::UnknownParent {
+            name: "Quadruped".to_string()
+        }
+    );
+    tree.record(root.clone(), 0).unwrap();
+    assert_eq!(
+        tree.record(root, 0).unwrap_err(),
+        PhylogenyError::DuplicateSpecies {
+            name: "Quadruped".to_string()
+        }
+    );
+    assert!(tree.record(a, 5).is_ok());
+}
+
+#[test]
+fn changes_name_the_gene_that_changed() {
+    let root = common::quadruped();
+    let mut genes = common::quadruped_genes();
+    genes.name = "Long Neck".to_string();
+    genes.parent = Some("Quadruped".to_string());
+    genes.head.bone.length.max = common::length(0.5);
+    genes.torso.spine.appendage_sockets.pop();
+    let daughter = Species::new(genes).unwrap();
+
+    let mut tree = Phylogeny::new();
+    tree.record(root, 0).unwrap();
+    let id = tree.record(daughter, 1).unwrap();
+    let changes = &tree.get(id).changes;
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(
+        changes[0],
+        GeneChange {
+            path: "head.bone.length".to_string(),
+            before: Some(format!("{:?}", common::bone(0.3, 0.4).length)),
+            after: Some(format!("{:?}", common::bone(0.3, 0.5).length)),
+        }
+    );
+    assert_eq!(changes[1].path, "torso.spine.appendage_sockets[2]");
+    assert!(changes[1].before.is_some() && changes[1].after.is_none());
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use bevy::{
+    mesh::{MeshPlugin, skinning::SkinnedMesh},
+    prelude::*,
+};
+use bevy_speciation::{
+    SpeciationPlugin, Species,
+    asset::{GeneratedOrganism, SpawnOrganism},
+    plugin::{OrganismSkeleton, SkeletonBone},
+    primitives::GenomeSeed,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        SpeciationPlugin::default(),
+    ));
+    app
+}
+
+#[test]
+fn spawns_one_entity_per_bone() {
+    let mut app = app();
+    let species = app
+        .world_mut()
+        .resource_mut::<Assets<Species>>()
+        .add(common::quadruped());
+    let organism = app
+        .world_mut()
+        .spawn(SpawnOrganism {
+            species,
+            seed: GenomeSeed(7),
+        })
+        .id();
+
+    app.update();
+
+    let world = app.world_mut();
+    assert!(world.get::<GeneratedOrganism>(organism).is_some());
+    let skeleton = world.get::<OrganismSkeleton>(organism).unwrap().clone();
+    assert_eq!(skeleton.bones.len(), skeleton.skeleton.bone_count());
+
+    let mut bones = world.query::<(&Name, &SkeletonBone, &Transform)>();
+    assert_eq!(bones.iter(world).count(), skeleton.skeleton.bone_count());
+
+    for (node, entity) in skeleton.skeleton.iter().zip(&skeleton.bones) {
+        let (name, bone, transform) = bones.get(world, *entity).unwrap();
+        assert_eq!(name.as_str(), node.id.name());
+        assert_eq!(bone.id, node.id);
+        assert_eq!(transform.translation, node.rest.translation);
+    }
+
+    let root = skeleton.bones[0];
+    assert_eq!(world.get::<ChildOf>(root).unwrap().parent(), organism);
+}
+
+#[test]
+fn rebuilds_skeleton_when_seed_changes() {
+    let mut app = app();
+    let species = app
+        .world_mut()
+        .resource_mut::<Assets<Species>>()
+        .add(common::quadruped());
+    let organism = app
+        .world_mut()
+        .spawn(SpawnOrganism {
+            species,
+            seed: GenomeSeed(1),
+        })
+        .id();
+    app.update();
+
+    app.world_mut()
+        .get_mut::<SpawnOrganism>(organism)
+        .unwrap()
+        .seed = GenomeSeed(2);
+    app.update();
+
+    let world = app.world_mut();
+    let skeleton = world.get::<OrganismSkeleton>(organism).unwrap();
+    let expected = skeleton.skeleton.bone_count();
+    let mut bones = world.query::<&SkeletonBone>();
+    assert_eq!(bones.iter(world).count(), expected);
+}
+
+#[test]
+fn skins_a_mesh_to_the_bone_entities() {
+    let mut app = app();
+    app.add_plugins(MeshPlugin);
+    let species = app
+        .world_mut()
+        .resource_mut::<Assets<Species>>()
+        .add(common::quadruped());
+    let organism = app
+        .world_mut()
+        .spawn(SpawnOrganism {
+            species,
+            seed: GenomeSeed(3),
+        })
+        .id();
+    app.update();
+
+    let world = app.world();
+    let skeleton = world.get::<OrganismSkeleton>(organism).unwrap();
+    let skinned = world.get::<SkinnedMesh>(organism).unwrap();
+    assert_eq!(skinned.joints, skeleton.bones);
+
+    let mesh = world.get::<Mesh3d>(organism).unwrap();
+    let mesh = world.resource::<Assets<Mesh>>().get(&mesh.0).unwrap();
+    assert!(mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX).is_some());
+}
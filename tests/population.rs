@@ -0,0 +1,122 @@
+mod common;
+
+use bevy_speciation::{
+    Organism, Species,
+    mutation::MutationRates,
+    population::{Population, PopulationConfig, Selection, SpeciationConfig},
+    primitives::{GenomeSeed, NonEmpty, Normalized, VertebraIndex},
+};
+
+/// Favors long spines
+fn spine_length(_: &Species, organism: &Organism) -> f32 {
+    organism
+        .torso()
+        .spine
+        .vertebrae
+        .iter()
+        .map(|vertebra| vertebra.bone.length.value())
+        .sum()
+}
+
+fn config() -> PopulationConfig {
+    PopulationConfig {
+        size: 12,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn evolution_is_deterministic_from_the_master_seed() {
+    let run = || {
+        let mut population = Population::new(common::quadruped(), config(), GenomeSeed(5)).unwrap();
+        population.run(4, spine_length).unwrap();
+        population
+            .organisms()
+            .map(|(_, organism)| format!("{organism:?}"))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn elitism_never_loses_the_best_organism() {
+    for selection in [Selection::Tournament { size: 3 }, Selection::Roulette] {
+        let mut population = Population::new(
+            common::quadruped(),
+            PopulationConfig {
+                selection,
+                ..config()
+            },
+            GenomeSeed(1),
+        )
+        .unwrap();
+        let reports = population.run(10, spine_length).unwrap();
+
+        for pair in reports.windows(2) {
+            assert!(pair[1].fitness[0].best >= pair[0].fitness[0].best);
+        }
+        assert!(reports.last().unwrap().fitness[0].mean > reports[0].fitness[0].mean);
+        assert_eq!(population.generation(), 10);
+        assert_eq!(population.organisms().count(), 12);
+    }
+}
+
+#[test]
+fn diverged_organisms_found_new_species() {
+    let mut population = Population::new(
+        common::quadruped(),
+        PopulationConfig {
+            speciation: Some(SpeciationConfig {
+                threshold: 0.05,
+                max_species: 3,
+                ..Default::default()
+            }),
+            ..config()
+        },
+        GenomeSeed(3),
+    )
+    .unwrap();
+    let reports = population.run(6, spine_length).unwrap();
+
+    let founded: Vec<_> = reports
+        .iter()
+        .flat_map(|report| report.new_species.clone())
+        .collect();
+    assert!(!founded.is_empty());
+    assert_eq!(population.subpopulations().len(), 3);
+    assert_eq!(population.phylogeny().len(), 3);
+
+    let root = population.subpopulations()[0].species();
+    for id in founded {
+        assert_eq!(population.phylogeny().get(id).parent, Some(root));
+    }
+}
+
+#[test]
+fn required_sockets_survive_many_generations() {
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.appendage_sockets[1].vertebra_indices = NonEmpty::new(VertebraIndex(5));
+    let species = Species::new(genes).unwrap();
+    let mut population = Population::new(
+        species.clone(),
+        PopulationConfig {
+            mutation: MutationRates {
+                vertebra_count: Normalized::new(1.0).unwrap(),
+                ..Default::default()
+            },
+            ..config()
+        },
+        GenomeSeed(9),
+    )
+    .unwrap();
+
+    // Favors short spines, pushing against the required hindlimbs
+    population
+        .run(40, |species, organism| -spine_length(species, organism))
+        .unwrap();
+    assert_eq!(population.generation(), 40);
+    for (_, organism) in population.organisms() {
+        assert!(organism.torso().spine.vertebrae.len() >= 6);
+        assert!(Organism::validate(organism.clone().into(), &species).is_ok());
+    }
+}
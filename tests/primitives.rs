@@ -0,0 +1,128 @@
+use bevy::prelude::LinearRgba;
+use bevy_speciation::primitives::*;
+
+fn curve(points: &[(f32, f32)]) -> Curve {
+    Curve::new(
+        points
+            .iter()
+            .map(|&(t, value)| CurvePoint {
+                t: Normalized::new(t).unwrap(),
+                value,
+            })
+            .collect(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn ranges_sample_within_their_bounds() {
+    let mut rng: GenomeRng = GenomeSeed(7).into();
+    let lengths = ValueRange {
+        min: Length::new(0.2).unwrap(),
+        max: Length::new(0.5).unwrap(),
+    };
+    let colors = ValueRange {
+        min: LinearRgba::rgb(0.1, 0.2, 0.3),
+        max: LinearRgba::rgb(0.4, 0.2, 0.9),
+    };
+    let counts = InclusiveRange::new(Count::new(2), Count::new(4));
+
+    let mut seen = [false; 3];
+    for _ in 0..200 {
+        assert!((0.2..=0.5).contains(&lengths.sample(&mut rng).value()));
+        let color = colors.sample(&mut rng);
+        assert!((0.1..=0.4).contains(&color.red));
+        assert!((color.green - 0.2).abs() < 1e-6);
+        assert!((0.3..=0.9).contains(&color.blue));
+        seen[counts.sample(&mut rng).value() as usize - 2] = true;
+    }
+    assert_eq!(seen, [true; 3]);
+}
+
+#[test]
+fn curves_sample_point_by_point() {
+    let range = ValueRange {
+        min: curve(&[(0.0, 1.0), (0.4, 0.0), (1.0, 2.0)]),
+        max: curve(&[(0.0, 3.0), (0.6, 1.0), (1.0, 2.0)]),
+    };
+    let mut rng: GenomeRng = GenomeSeed(3).into();
+    for _ in 0..100 {
+        let sampled = range.sample(&mut rng);
+        let points = sampled.points();
+        assert_eq!(points.len(), 3);
+        assert!((1.0..=3.0).contains(&points[0].value));
+        assert!((0.0..=1.0).contains(&points[1].value));
+        assert!((0.4..=0.6).contains(&points[1].t.value()));
+        assert_eq!(points[2].value, 2.0);
+    }
+}
+
+#[test]
+fn the_same_seed_samples_the_same_values() {
+    let range = ValueRange {
+        min: Normalized::new(0.0).unwrap(),
+        max: Normalized::new(1.0).unwrap(),
+    };
+    let draw = |seed| {
+        let mut rng: GenomeRng = GenomeSeed(seed).into();
+        (0..8).map(|_| range.sample(&mut rng)).collect::<Vec<_>>()
+    };
+    assert_eq!(draw(11), draw(11));
+    assert_ne!(draw(11), draw(12));
+}
+
+fn paws_claws_hooves(weights: [f32; 3]) -> WeightedChoice<&'static str> {
+    WeightedChoice::from_vec(
+        ["paw", "claw", "hoof"]
+            .into_iter()
+            .zip(weights)
+            .map(|(value, weight)| Weighted::new(value, weight))
+            .collect(),
+    )
+    .unwrap()
+}
+
+fn tally(choice: &WeightedChoice<&'static str>, seed: u64) -> [usize; 3] {
+    let mut rng: GenomeRng = GenomeSeed(seed).into();
+    let mut counts = [0; 3];
+    for _ in 0..10_000 {
+        let chosen = choice.choose(&mut rng);
+        let index = ["paw", "claw", "hoof"]
+            .iter()
+            .position(|value| value == chosen)
+            .unwrap();
+        counts[index] += 1;
+    }
+    counts
+}
+
+#[test]
+fn choices_follow_their_weights() {
+    let counts = tally(&paws_claws_hooves([0.7, 0.25, 0.05]), 5);
+    for (count, expected) in counts.iter().zip([7000, 2500, 500]) {
+        assert!(count.abs_diff(expected) < 200, "{counts:?}");
+    }
+    assert_eq!(
+        tally(&paws_claws_hooves([0.0, 1.0, 0.0]), 5),
+        [0, 10_000, 0]
+    );
+}
+
+#[test]
+fn invalid_weights_fall_back_to_a_uniform_pick() {
+    for weights in [[0.0; 3], [1.0, -1.0, 0.5], [f32::NAN, 1.0, 1.0]] {
+        let choice = paws_claws_hooves(weights);
+        assert!(!choice.has_valid_weights());
+        for count in tally(&choice, 9) {
+            assert!(count.abs_diff(3333) < 200);
+        }
+    }
+}
+
+#[test]
+fn lengths_are_positive_and_finite() {
+    assert!(Length::new(0.5).is_some());
+    for value in [0.0, -1.0, f32::INFINITY, f32::NAN] {
+        assert!(Length::new(value).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::Vec3;
+use bevy_speciation::{
+    Organism, Species,
+    appendage::AppendageClass,
+    crossover::CrossoverPolicy,
+    mutation::MutationRates,
+    organism::UnvalidatedOrganism,
+    primitives::*,
+    skeleton::{BoneClass, SkeletonConfig, SkeletonGenerator},
+    sockets_symmetry::{BodySymmetry, SymmetricSocket},
+    validation_errors::{OrganismValidationError, SpeciesValidationError},
+};
+
+const FOLD_ANGLE: f32 = TAU / 5.0;
+
+#[test]
+fn radial_sockets_repeat_evenly_around_the_body_axis() {
+    let species = common::starfish();
+    for seed in 0..10 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+        let SymmetricSocket::Radial(folds) = &organism.torso().spine.appendages[0].socket else {
+            panic!("expected a radial socket");
+        };
+        assert_eq!(folds.len(), 5);
+
+        for (a, b) in folds.iter().zip(folds.iter().skip(1)) {
+            let (a_position, b_position) = (a.position.as_vec3(), b.position.as_vec3());
+            assert!((a_position.length() - b_position.length()).abs() < 1e-5);
+            assert!((a_position.angle_between(b_position) - FOLD_ANGLE).abs() < 1e-4);
+            assert!((a.normal.angle_between(*b.normal) - FOLD_ANGLE).abs() < 1e-4);
+            assert_eq!(format!("{:?}", a.attachment), format!("{:?}", b.attachment));
+        }
+        assert!(Organism::validate(organism.into(), &species).is_ok());
+    }
+}
+
+#[test]
+fn folds_stay_alike_through_crossover_and_mutation() {
+    let species = common::starfish();
+    let a = species.generate(GenomeSeed(1)).unwrap();
+    let b = species.generate(GenomeSeed(2)).unwrap();
+    let child = species
+        .crossover(&a, &b, CrossoverPolicy::Blend, 0)
+        .unwrap();
+    let mutant = species
+        .mutate(&child, &MutationRates::default(), 0)
+        .unwrap();
+
+    for organism in [&child, &mutant] {
+        let SymmetricSocket::Radial(folds) = &organism.torso().spine.appendages[0].socket else {
+            panic!("expected a radial socket");
+        };
+        assert_eq!(folds.len(), 5);
+        let first = format!("{:?}", folds[0].attachment);
+        assert!(
+            folds
+                .iter()
+                .all(|fold| format!("{:?}", fold.attachment) == first)
+        );
+    }
+}
+
+#[test]
+fn skeleton_names_each_fold_and_turns_it_about_the_axis() {
+    let organism = common::starfish().generate(GenomeSeed(3)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+
+    let arms: Vec<_> = skeleton
+        .root
+        .iter_depth_first()
+        .filter(|node| {
+            node.id.class == BoneClass::Limb(AppendageClass::Tentacle)
+                && node.id.index == 0
+                && node.id.branch_path.is_empty()
+        })
+        .collect();
+    assert_eq!(
+        arms.iter().map(|arm| arm.id.fold).collect::<Vec<_>>(),
+        (0..5).map(Some).collect::<Vec<_>>()
+    );
+    assert_eq!(arms[2].id.name(), "tentacle_0_v0_s0_f2");
+
+    let mut names: Vec<_> = skeleton
+        .root
+        .iter_depth_first()
+        .map(|node| node.id.name())
+        .collect();
+    let bones = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), bones);
+
+    let SymmetricSocket::Radial(folds) = &organism.torso().spine.appendages[0].socket else {
+        panic!("expected a radial socket");
+    };
+    for (arm, fold) in arms.iter().zip(folds) {
+        assert!(
+            arm.rest
+                .translation
+                .abs_diff_eq(fold.position.as_vec3(), 1e-5)
+        );
+        assert!((arm.rest.rotation * Vec3::NEG_Y).abs_diff_eq(fold.normal.as_vec3(), 1e-5));
+    }
+    for (a, b) in arms.iter().zip(arms.iter().skip(1)) {
+        let (a_out, b_out) = (a.rest.rotation * Vec3::X, b.rest.rotation * Vec3::X);
+        assert!((a_out.angle_between(b_out) - FOLD_ANGLE).abs() < 1e-4);
+    }
+
+    // Folds turn about the axis the sockets were spread around, whatever way
+    // the spine extends
+    let skeleton = SkeletonGenerator::new(SkeletonConfig {
+        bone_axis: Vec3::Y,
+        ..SkeletonConfig::default()
+    })
+    .generate(&organism);
+    for (fold, socket) in folds.iter().enumerate() {
+        let arm = skeleton
+            .root
+            .iter_depth_first()
+            .find(|node| node.id == arms[fold].id)
+            .unwrap();
+        assert!((arm.rest.rotation * Vec3::NEG_Y).abs_diff_eq(socket.normal.as_vec3(), 1e-5));
+    }
+}
+
+#[test]
+fn fold_counts_are_checked() {
+    let mut genes = common::starfish_genes();
+    genes.symmetry = BodySymmetry::Radial {
+        fold_count: Count::new(1),
+    };
+    assert_eq!(
+        Species::new(genes).unwrap_err().errors(),
+        [SpeciesValidationError::TooFewFolds {
+            path: "symmetry".to_string(),
+            fold_count: Count::new(1),
+        }]
+    );
+
+    let mut genes = common::starfish_genes();
+    genes.symmetry = BodySymmetry::Bilateral;
+    assert_eq!(
+        Species::new(genes).unwrap_err().errors(),
+        [SpeciesValidationError::RadialOnNonRadial {
+            path: "torso.spine.appendage_sockets[0].placement".to_string(),
+        }]
+    );
+
+    let species = common::starfish();
+    let mut organism: UnvalidatedOrganism = species.generate(GenomeSeed(0)).unwrap().into();
+    let SymmetricSocket::Radial(folds) = &mut organism.torso.spine.appendages[0].socket else {
+        panic!("expected a radial socket");
+    };
+    folds.pop();
+    assert_eq!(
+        Organism::validate(organism, &species).unwrap_err().errors(),
+        [OrganismValidationError::FoldCountMismatch {
+            path: "torso.spine.appendages[0]".to_string(),
+            expected: 5,
+            found: 4,
+        }]
+    );
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "ron")]
+
+mod common;
+
+use bevy_speciation::{
+    Organism, Species, UnvalidatedSpecies, organism::UnvalidatedOrganism, primitives::*,
+    skeleton::SkeletonGenerator,
+};
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    ron::from_str(&ron::to_string(value).unwrap()).unwrap()
+}
+
+#[test]
+fn species_and_organisms_round_trip() {
+    let species = common::quadruped();
+    let loaded: Species = round_trip(&species);
+    assert_eq!(format!("{species:?}"), format!("{loaded:?}"));
+
+    let organism = species.generate(GenomeSeed(8)).unwrap();
+    let text = ron::to_string(&organism).unwrap();
+    let loaded: UnvalidatedOrganism = ron::from_str(&text).unwrap();
+    let loaded = Organism::validate(loaded, &species).unwrap();
+    assert_eq!(format!("{organism:?}"), format!("{loaded:?}"));
+
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    assert_eq!(
+        format!("{skeleton:?}"),
+        format!("{:?}", round_trip(&skeleton))
+    );
+}
+
+#[test]
+fn primitives_round_trip() {
+    let curve = Curve::new(vec![
+        CurvePoint {
+            t: Normalized::new(0.0).unwrap(),
+            value: 1.5,
+        },
+        CurvePoint {
+            t: Normalized::new(0.7).unwrap(),
+            value: -0.25,
+        },
+    ])
+    .unwrap()
+    .with_interpolation(Interpolation::MonotoneCubic);
+    assert_eq!(round_trip(&curve), curve);
+
+    let mut choice = WeightedChoice::new(Count::new(2), 0.7);
+    choice.push(Count::new(5), 0.3);
+    let loaded = round_trip(&choice);
+    assert_eq!(
+        loaded.iter().collect::<Vec<_>>(),
+        choice.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn invariants_are_checked_while_deserializing() {
+    assert!(ron::from_str::<Length>("0.0").is_err());
+    assert!(ron::from_str::<Length>("-1.0").is_err());
+    assert!(ron::from_str::<Normalized>("1.5").is_err());
+    assert!(ron::from_str::<NonEmpty<u8>>("[]").is_err());
+    assert!(
+        ron::from_str::<Curve>("(points: [(t: 0.2, value: 0.0), (t: 0.5, value: 1.0)])").is_ok()
+    );
+    assert!(
+        ron::from_str::<Curve>("(points: [(t: 0.5, value: 0.0), (t: 0.2, value: 1.0)])").is_err()
+    );
+    assert!(
+        ron::from_str::<Curve>("(points: [(t: 0.5, value: 0.0), (t: 0.5, value: 1.0)])").is_err()
+    );
+
+    // Species deserialize through `Species::new`, so gene-level problems are
+    // rejected too
+    let with_genes = |edit: fn(&mut UnvalidatedSpecies)| {
+        let mut genes = common::quadruped_genes();
+        edit(&mut genes);
+        ron::from_str::<Species>(&ron::to_string(&genes).unwrap())
+    };
+    assert!(with_genes(|_| {}).is_ok());
+    assert!(
+        with_genes(|genes| {
+            genes.torso.spine.appendage_sockets[0].allowed = WeightedChoice::new(
+                common::leg(bevy_speciation::appendage::AppendageClass::Forelimb),
+                -1.0,
+            );
+        })
+        .is_err()
+    );
+    assert!(
+        with_genes(|genes| {
+            genes.torso.spine.vertebra_count = InclusiveRange::new(Count::new(0), Count::new(0));
+        })
+        .is_err()
+    );
+}
@@ -0,0 +1,206 @@
+mod common;
+
+use bevy::prelude::{Dir3, Vec3};
+use bevy_speciation::{
+    Organism, Species,
+    anatomical_features::MandibleStructure,
+    appendage::AppendageClass,
+    organism::UnvalidatedOrganism,
+    primitives::*,
+    skeletal::{ArticulationRange, Bone, BoneSegment, Joint, JointArticulation},
+    skeleton::{BoneClass, BoneId, Side, SkeletonConfig, SkeletonGenerator, SkeletonNode},
+    sockets_symmetry::SymmetricSocket,
+    tissue_muscle::TissueEnvelope,
+};
+
+fn segment(length: f32, jointed: bool) -> BoneSegment {
+    let range = ArticulationRange {
+        min: Radians::new(-0.5),
+        max: Radians::new(0.5),
+    };
+    BoneSegment {
+        bone: Bone {
+            length: common::length(length),
+            tissue: TissueEnvelope {
+                profile: CrossSectionProfile::Circular,
+                radius_curve: common::taper(0.02),
+                musculature: Vec::new(),
+            },
+        },
+        distal_joint: jointed.then_some(Joint {
+            articulation: JointArticulation {
+                flexion: range,
+                rotation: range,
+                abduction: range,
+            },
+        }),
+    }
+}
+
+/// The quadruped with a three-bone jaw, jointed after its middle bone
+fn jawed() -> Species {
+    let mut genes = common::quadruped_genes();
+    genes.head.mandible = Some(WeightedChoice::new(
+        MandibleStructure {
+            segments: vec![
+                segment(0.2, false),
+                segment(0.15, true),
+                segment(0.1, false),
+            ],
+        },
+        1.0,
+    ));
+    Species::new(genes).unwrap()
+}
+
+fn mandible(index: u8) -> BoneId {
+    BoneId {
+        index,
+        ..BoneId::mandible()
+    }
+}
+
+#[test]
+fn mandible_segments_form_a_chain() {
+    let organism = jawed().generate(GenomeSeed(0)).unwrap();
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+
+    let head = skeleton.root.find(&BoneId::head()).unwrap();
+    let jaw = head.children.iter().find(|node| node.id == mandible(0));
+    let chain: Vec<&SkeletonNode> =
+        std::iter::successors(jaw, |node| node.children.first()).collect();
+    assert_eq!(
+        chain.iter().map(|node| node.id.clone()).collect::<Vec<_>>(),
+        [mandible(0), mandible(1), mandible(2)]
+    );
+    assert!(chain.iter().all(|node| node.children.len() <= 1));
+
+    assert_eq!(
+        chain
+            .iter()
+            .map(|node| node.articulation.is_some())
+            .collect::<Vec<_>>(),
+        [false, true, false]
+    );
+    assert!(
+        chain[1]
+            .rest
+            .translation
+            .abs_diff_eq(Vec3::NEG_Y * 0.2, 1e-6)
+    );
+    assert!(
+        chain[2]
+            .rest
+            .translation
+            .abs_diff_eq(Vec3::NEG_Y * 0.15, 1e-6)
+    );
+}
+
+#[test]
+fn mandible_hangs_from_its_socket() {
+    let species = jawed();
+    let mut organism: UnvalidatedOrganism = species.generate(GenomeSeed(0)).unwrap().into();
+    let socket = organism.head.mandible_socket.as_mut().unwrap();
+    socket.position = LocalPosition::new(0.0, -0.1, 0.3);
+    socket.normal = Dir3::NEG_Z;
+    let organism = Organism::validate(organism, &species).unwrap();
+
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let jaw = skeleton.root.find(&mandible(0)).unwrap();
+    assert_eq!(jaw.rest.translation, Vec3::new(0.0, -0.1, 0.3));
+    assert_eq!(jaw.axis, Vec3::NEG_Z);
+    let next = skeleton.root.find(&mandible(1)).unwrap();
+    assert!(next.rest.translation.abs_diff_eq(Vec3::NEG_Z * 0.2, 1e-6));
+}
+
+#[test]
+fn limbs_start_at_their_socket_and_extend_along_its_normal() {
+    let species = common::quadruped();
+    let mut organism: UnvalidatedOrganism = species.generate(GenomeSeed(4)).unwrap().into();
+    let SymmetricSocket::Lateral(pair) = &mut organism.torso.spine.appendages[0].socket else {
+        panic!("expected a lateral pair");
+    };
+    pair.left.position = LocalPosition::new(0.4, 0.0, -0.05);
+    pair.left.normal = Dir3::X;
+    let organism = Organism::validate(organism, &species).unwrap();
+
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let limb = |side| {
+        skeleton
+            .root
+            .find(
+                &BoneId::limb(AppendageClass::Forelimb, Some(side), 0)
+                    .with_vertebra(0)
+                    .with_socket(0),
+            )
+            .unwrap()
+    };
+    let (left, right) = (limb(Side::Left), limb(Side::Right));
+    assert_eq!(left.rest.translation, Vec3::new(0.4, 0.0, -0.05));
+    assert!((left.rest.rotation * left.axis).abs_diff_eq(Vec3::X, 1e-6));
+    assert_eq!(right.rest.translation, Vec3::new(-0.3, -0.1, 0.0));
+    assert!((right.rest.rotation * right.axis).abs_diff_eq(Vec3::NEG_Y, 1e-6));
+
+    let vertebra = skeleton.root.find(&BoneId::spine(0)).unwrap();
+    assert!(vertebra.children.iter().any(|child| child.id == left.id));
+}
+
+#[test]
+fn limbs_facing_against_their_axis_roll_about_the_lateral_axis() {
+    let species = common::quadruped();
+    let mut organism: UnvalidatedOrganism = species.generate(GenomeSeed(4)).unwrap().into();
+    let SymmetricSocket::Lateral(pair) = &mut organism.torso.spine.appendages[0].socket else {
+        panic!("expected a lateral pair");
+    };
+    pair.left.normal = Dir3::Y;
+    let organism = Organism::validate(organism, &species).unwrap();
+
+    let skeleton = SkeletonGenerator::with_default_config().generate(&organism);
+    let limb = skeleton
+        .root
+        .find(
+            &BoneId::limb(AppendageClass::Forelimb, Some(Side::Left), 0)
+                .with_vertebra(0)
+                .with_socket(0),
+        )
+        .unwrap();
+    assert!((limb.rest.rotation * limb.axis).abs_diff_eq(Vec3::Y, 1e-6));
+    assert!((limb.rest.rotation * Vec3::X).abs_diff_eq(Vec3::X, 1e-6));
+}
+
+#[test]
+fn right_side_spreads_mirror_across_the_same_plane_as_right_sockets() {
+    let generator = SkeletonGenerator::new(SkeletonConfig {
+        lateral_axis: Vec3::new(1.0, 1.0, 0.0),
+        ..SkeletonConfig::default()
+    });
+    let mirror = Vec3::new(-1.0, 1.0, 1.0);
+
+    let mut spread = false;
+    for seed in 0..5 {
+        let organism = common::quadruped().generate(GenomeSeed(seed)).unwrap();
+        let skeleton = generator.generate(&organism);
+        let bones = |class, side| -> Vec<Vec3> {
+            skeleton
+                .root
+                .iter_depth_first()
+                .filter(|node| node.id.class == class && node.id.side == Some(side))
+                .map(|node| node.rest.translation)
+                .collect()
+        };
+        for class in [
+            BoneClass::Limb(AppendageClass::Forelimb),
+            BoneClass::Limb(AppendageClass::Hindlimb),
+            BoneClass::Digit(AppendageClass::Forelimb),
+            BoneClass::Digit(AppendageClass::Hindlimb),
+        ] {
+            let (left, right) = (bones(class, Side::Left), bones(class, Side::Right));
+            assert_eq!(left.len(), right.len());
+            for (left, right) in left.iter().zip(&right) {
+                assert!(right.abs_diff_eq(*left * mirror, 1e-6));
+                spread |= matches!(class, BoneClass::Digit(_)) && left.x != 0.0;
+            }
+        }
+    }
+    assert!(spread);
+}
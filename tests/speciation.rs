@@ -0,0 +1,95 @@
+mod common;
+
+use bevy_speciation::{
+    mutation::SpeciesMutationRates,
+    primitives::{GenomeSeed, Normalized},
+    speciation::DivergenceConfig,
+};
+
+fn rates(value: f32) -> DivergenceConfig {
+    let rate = Normalized::new(value).unwrap();
+    DivergenceConfig {
+        mutation: SpeciesMutationRates {
+            range: rate,
+            allowed_appendage: rate,
+            required: rate,
+            step: Normalized::new(0.2).unwrap(),
+        },
+        shift: rate,
+        shift_step: Normalized::new(0.2).unwrap(),
+        new_socket: rate,
+        removed_socket: rate,
+        symmetry: rate,
+        name: None,
+    }
+}
+
+#[test]
+fn daughters_link_to_their_parent() {
+    let parent = common::quadruped();
+    let daughter = parent
+        .diverge(&DivergenceConfig::default(), GenomeSeed(1))
+        .unwrap();
+    let again = parent
+        .diverge(&DivergenceConfig::default(), GenomeSeed(1))
+        .unwrap();
+    let sister = parent
+        .diverge(&DivergenceConfig::default(), GenomeSeed(2))
+        .unwrap();
+
+    assert_eq!(daughter.parent(), Some(parent.name()));
+    assert!(daughter.name().starts_with(parent.name()));
+    assert_eq!(daughter.name(), again.name());
+    assert_ne!(daughter.name(), sister.name());
+    assert_eq!(parent.parent(), None);
+
+    let named = parent
+        .diverge(
+            &DivergenceConfig {
+                name: Some("Strider".to_string()),
+                ..Default::default()
+            },
+            GenomeSeed(1),
+        )
+        .unwrap();
+    assert_eq!(named.name(), "Strider");
+}
+
+#[test]
+fn divergence_without_chance_keeps_the_genes() {
+    let parent = common::quadruped();
+    let daughter = parent.diverge(&rates(0.0), GenomeSeed(5)).unwrap();
+
+    assert_eq!(daughter.symmetry(), parent.symmetry());
+    assert_eq!(
+        format!("{:?}", daughter.torso()),
+        format!("{:?}", parent.torso())
+    );
+    assert_eq!(
+        format!("{:?}", daughter.head()),
+        format!("{:?}", parent.head())
+    );
+}
+
+#[test]
+fn full_divergence_reshapes_the_body_plan() {
+    let parent = common::quadruped();
+    for seed in 0..10 {
+        let daughter = parent.diverge(&rates(1.0), GenomeSeed(seed)).unwrap();
+
+        assert_ne!(daughter.symmetry(), parent.symmetry());
+        assert_ne!(
+            daughter.torso().spine.vertebra.length.min,
+            parent.torso().spine.vertebra.length.min
+        );
+        // Optional sockets are all dropped, then one copy of a rule is added
+        let optional = daughter
+            .torso()
+            .spine
+            .appendage_sockets
+            .iter()
+            .filter(|rule| !rule.required)
+            .count();
+        assert!(optional <= 1);
+    }
+}
@@ -0,0 +1,304 @@
+mod common;
+
+use bevy_speciation::{
+    Organism, Species, UnvalidatedSpecies,
+    appendage::{AppendageClass, Terminus},
+    organism::UnvalidatedOrganism,
+    primitives::*,
+    sockets_symmetry::{BodySymmetry, SymmetricSocket},
+    species::SideGenes,
+    surface::SurfacePattern,
+    validation_errors::{
+        Constraint, GenerationError, OrganismValidationError, SpeciesValidationError,
+    },
+};
+
+fn errors(genes: UnvalidatedSpecies) -> Vec<SpeciesValidationError> {
+    Species::new(genes).unwrap_err().into_iter().collect()
+}
+
+#[test]
+fn reports_every_problem_with_its_path() {
+    let mut genes = common::quadruped_genes();
+    let spine = &mut genes.torso.spine;
+    spine.vertebra_count = InclusiveRange::new(Count::new(6), Count::new(4));
+    spine.vertebra.length = ValueRange {
+        min: common::length(0.5),
+        max: common::length(0.1),
+    };
+    spine.appendage_sockets[2].vertebra_indices = NonEmpty::new(VertebraIndex(9));
+    let tail = spine.appendage_sockets[2]
+        .allowed
+        .values_mut()
+        .next()
+        .unwrap();
+    tail.limb.segment_count = InclusiveRange::new(Count::new(0), Count::new(0));
+    tail.limb.branching_probability = common::normalized(0.5);
+    genes.head.integument.allowed_patterns = WeightedChoice::new(SurfacePattern::Smooth, 0.0);
+    genes
+        .torso
+        .integument
+        .allowed_patterns
+        .push(SurfacePattern::Warty, -1.0);
+
+    let path = |path: &str| path.to_string();
+    assert_eq!(
+        errors(genes),
+        vec![
+            SpeciesValidationError::EmptyAllowedList {
+                path: path("head.integument.allowed_patterns")
+            },
+            SpeciesValidationError::EmptyVertebraRange {
+                path: path("torso.spine.vertebra_count")
+            },
+            SpeciesValidationError::InvertedRange {
+                path: path("torso.spine.vertebra.length")
+            },
+            SpeciesValidationError::SocketIndexOutOfBounds {
+                path: path("torso.spine.appendage_sockets[2].vertebra_indices[0]"),
+                socket_index: VertebraIndex(9),
+                max_vertebrae: Count::new(4),
+            },
+            SpeciesValidationError::BranchingWithoutSegments {
+                path: path(
+                    "torso.spine.appendage_sockets[2].allowed[0].limb.branching_probability"
+                )
+            },
+            SpeciesValidationError::InvalidWeights {
+                path: path("torso.integument.allowed_patterns")
+            },
+        ]
+    );
+}
+
+#[test]
+fn only_asymmetric_bodies_have_uneven_sides() {
+    let mut genes = common::quadruped_genes();
+    let sockets = &mut genes.torso.spine.appendage_sockets;
+    sockets[0].sides = SideGenes::Varied {
+        variation: common::normalized(0.2),
+    };
+    sockets[2].sides = SideGenes::Independent {
+        right: Box::new(WeightedChoice::new(common::tail(), 0.0)),
+    };
+
+    assert_eq!(
+        errors(genes.clone()),
+        [
+            SpeciesValidationError::UnevenSides {
+                path: "torso.spine.appendage_sockets[0].sides".into()
+            },
+            SpeciesValidationError::UnevenSides {
+                path: "torso.spine.appendage_sockets[2].sides".into()
+            },
+            SpeciesValidationError::EmptyAllowedList {
+                path: "torso.spine.appendage_sockets[2].sides.right".into()
+            },
+        ]
+    );
+
+    genes.symmetry = BodySymmetry::Asymmetric;
+    assert_eq!(
+        errors(genes),
+        [
+            SpeciesValidationError::UnevenSides {
+                path: "torso.spine.appendage_sockets[2].sides".into()
+            },
+            SpeciesValidationError::EmptyAllowedList {
+                path: "torso.spine.appendage_sockets[2].sides.right".into()
+            },
+        ]
+    );
+}
+
+#[test]
+fn inverted_gene_ranges_are_rejected() {
+    let mut genes = common::quadruped_genes();
+    let leg = genes.torso.spine.appendage_sockets[0]
+        .allowed
+        .values_mut()
+        .next()
+        .unwrap();
+    leg.limb.segment.length = ValueRange {
+        min: common::length(0.9),
+        max: common::length(0.3),
+    };
+    leg.integument.roughness.min.0 = common::normalized(1.0);
+    genes.torso.base_tissue.bulge_count = InclusiveRange::new(Count::new(3), Count::new(1));
+    genes.head.integument.base_color.max.alpha = 0.5;
+
+    assert_eq!(
+        errors(genes),
+        vec![
+            SpeciesValidationError::InvertedRange {
+                path: "head.integument.base_color".into()
+            },
+            SpeciesValidationError::InvertedRange {
+                path: "torso.spine.appendage_sockets[0].allowed[0].limb.segment.length".into()
+            },
+            SpeciesValidationError::InvertedRange {
+                path: "torso.spine.appendage_sockets[0].allowed[0].integument.roughness".into()
+            },
+            SpeciesValidationError::InvertedRange {
+                path: "torso.base_tissue.bulge_count".into()
+            },
+        ]
+    );
+    assert!(Species::new(common::quadruped_genes()).is_ok());
+
+    let mut organism = generated(0);
+    organism.head.integument.base_color.alpha = 0.5;
+    assert_eq!(
+        organism_errors(organism),
+        [OrganismValidationError::OutOfRange {
+            path: "head.integument.base_color".into()
+        }]
+    );
+}
+
+fn organism_errors(organism: UnvalidatedOrganism) -> Vec<OrganismValidationError> {
+    Organism::validate(organism, &common::quadruped())
+        .unwrap_err()
+        .into_iter()
+        .collect()
+}
+
+fn generated(seed: u64) -> UnvalidatedOrganism {
+    common::quadruped()
+        .generate(GenomeSeed(seed))
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn generated_organisms_validate() {
+    let species = common::quadruped();
+    for seed in 0..20 {
+        assert!(Organism::validate(generated(seed), &species).is_ok());
+    }
+}
+
+#[test]
+fn hand_edited_organisms_report_each_change() {
+    let mut organism = generated(1);
+    organism
+        .torso
+        .spine
+        .vertebrae
+        .iter_mut()
+        .next()
+        .unwrap()
+        .bone
+        .length = common::length(5.0);
+    let SymmetricSocket::Lateral(pair) = &mut organism.torso.spine.appendages[0].socket else {
+        panic!("forelimbs are lateral");
+    };
+    pair.right.attachment.as_mut().unwrap().structure.terminus = Terminus::Pincer;
+    pair.left.attachment.as_mut().unwrap().class = AppendageClass::Wing;
+    organism.head.integument.pattern = SurfacePattern::Chitinous;
+
+    assert_eq!(
+        organism_errors(organism),
+        vec![
+            OrganismValidationError::NotAllowed {
+                path: "head.integument.pattern".into()
+            },
+            OrganismValidationError::OutOfRange {
+                path: "torso.spine.vertebrae[0].bone.length".into()
+            },
+            OrganismValidationError::AppendageClassMismatch {
+                path: "torso.spine.appendages[0].left.class".into(),
+                found: AppendageClass::Wing,
+            },
+            OrganismValidationError::NotAllowed {
+                path: "torso.spine.appendages[0].right.structure.terminus".into()
+            },
+        ]
+    );
+}
+
+#[test]
+fn missing_sockets_and_body_plan_changes_are_caught() {
+    let mut organism = generated(2);
+    organism.symmetry = BodySymmetry::Asymmetric;
+    organism.head.sensory_sockets.clear();
+    let SymmetricSocket::Lateral(pair) = &mut organism.torso.spine.appendages[1].socket else {
+        panic!("hindlimbs are lateral");
+    };
+    pair.left.attachment = None;
+    while organism.torso.spine.vertebrae.len() < 10 {
+        let vertebra = organism.torso.spine.vertebrae.first().clone();
+        organism.torso.spine.vertebrae.push(vertebra);
+    }
+
+    assert_eq!(
+        organism_errors(organism),
+        vec![
+            OrganismValidationError::SymmetryMismatch {
+                expected: BodySymmetry::Bilateral,
+                found: BodySymmetry::Asymmetric,
+            },
+            OrganismValidationError::RequiredSocketEmpty {
+                path: "head.sensory_sockets[0]".into()
+            },
+            OrganismValidationError::VertebraCountOutOfRange {
+                count: 10,
+                min: Count::new(4),
+                max: Count::new(8),
+            },
+            OrganismValidationError::RequiredSocketEmpty {
+                path: "torso.spine.appendages[1].left".into()
+            },
+        ]
+    );
+}
+
+#[test]
+fn errors_have_readable_messages() {
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.vertebra.length = ValueRange {
+        min: common::length(0.5),
+        max: common::length(0.1),
+    };
+    genes.torso.spine.appendage_sockets[0].vertebra_indices = NonEmpty::new(VertebraIndex(9));
+    let err: Box<dyn std::error::Error> = Species::new(genes).unwrap_err().into();
+    assert_eq!(
+        err.to_string(),
+        "invalid species (2 problems): torso.spine.vertebra.length: minimum is above maximum; \
+         torso.spine.appendage_sockets[0].vertebra_indices[0]: vertebra 9 is past the last of \
+         at most 8 vertebrae"
+    );
+
+    let mut organism = generated(3);
+    organism.head.integument.pattern = SurfacePattern::Warty;
+    let err = Organism::validate(organism, &common::quadruped()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid organism: head.integument.pattern: not in the species' allowed list"
+    );
+}
+
+#[test]
+fn generation_redraws_vertebra_counts_for_required_sockets() {
+    let mut genes = common::quadruped_genes();
+    genes.torso.spine.appendage_sockets[2].vertebra_indices = NonEmpty::new(VertebraIndex(6));
+    genes.torso.spine.appendage_sockets[2].required = true;
+    let species = Species::new(genes.clone()).unwrap();
+    for seed in 0..20 {
+        let organism = species.generate(GenomeSeed(seed)).unwrap();
+        assert!(organism.torso().spine.vertebrae.len() >= 7);
+    }
+
+    let err = GenerationError::ConstraintUnsatisfiable {
+        rule: "torso.spine.appendage_sockets[2]".into(),
+        constraint: Constraint::SocketWithinSpine {
+            vertebra_index: VertebraIndex(6),
+        },
+        attempts: 16,
+    };
+    assert_eq!(
+        err.to_string(),
+        "torso.spine.appendage_sockets[2]: could not satisfy a required socket on vertebra 6 \
+         after 16 attempts"
+    );
+}